@@ -0,0 +1,95 @@
+// -- Base32 Encoding ----------------------------------------------
+// Lowercase RFC 4648 base32 without padding, used for URL-safe references.
+
+const ALPHABET: &[u8; 32] = b"abcdefghijklmnopqrstuvwxyz234567";
+
+/// Encodes bytes as unpadded, lowercase base32.
+///
+/// # Arguments
+///
+/// * `bytes` - The bytes to encode.
+///
+/// # Returns
+///
+/// * `String` - The encoded string.
+pub(crate) fn encode(bytes: &[u8]) -> String {
+  let mut output = String::with_capacity(bytes.len().div_ceil(5) * 8);
+  let mut buffer: u16 = 0;
+  let mut bits = 0;
+  for &byte in bytes {
+    buffer = (buffer << 8) | u16::from(byte);
+    bits += 8;
+    while bits >= 5 {
+      bits -= 5;
+      output.push(ALPHABET[usize::from((buffer >> bits) & 0x1f)] as char);
+    }
+  }
+  if bits > 0 {
+    output.push(ALPHABET[usize::from((buffer << (5 - bits)) & 0x1f)] as char);
+  }
+  output
+}
+
+/// Decodes unpadded base32, ignoring case.
+///
+/// # Arguments
+///
+/// * `value` - The string to decode.
+///
+/// # Returns
+///
+/// * `Option<Vec<u8>>` - The decoded bytes, or `None` if the string contains
+///   characters outside the alphabet or is not a canonical encoding.
+pub(crate) fn decode(value: &str) -> Option<Vec<u8>> {
+  let mut output = Vec::with_capacity(value.len() * 5 / 8);
+  let mut buffer: u16 = 0;
+  let mut bits = 0;
+  for c in value.bytes() {
+    let index = ALPHABET.iter().position(|&a| a == c.to_ascii_lowercase())?;
+    buffer = (buffer << 5) | index as u16;
+    bits += 5;
+    if bits >= 8 {
+      bits -= 8;
+      output.push((buffer >> bits) as u8);
+    }
+  }
+  // Leftover bits must be zero padding from the final character.
+  if bits >= 5 || buffer & ((1 << bits) - 1) != 0 {
+    return None;
+  }
+  Some(output)
+}
+
+// -- Tests ------------------------------------------------------------------
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn encode_rfc4648_vectors() {
+    assert_eq!(encode(b""), "");
+    assert_eq!(encode(b"f"), "my");
+    assert_eq!(encode(b"fo"), "mzxq");
+    assert_eq!(encode(b"foo"), "mzxw6");
+    assert_eq!(encode(b"foob"), "mzxw6yq");
+    assert_eq!(encode(b"fooba"), "mzxw6ytb");
+    assert_eq!(encode(b"foobar"), "mzxw6ytboi");
+  }
+
+  #[test]
+  fn decode_round_trip() {
+    for input in [&b""[..], b"f", b"fo", b"foo", b"foob", b"fooba", b"foobar"] {
+      assert_eq!(decode(&encode(input)).as_deref(), Some(input));
+    }
+    assert_eq!(decode("MZXW6").as_deref(), Some(&b"foo"[..]));
+  }
+
+  #[test]
+  fn decode_rejects_invalid_input() {
+    assert_eq!(decode("mzxw1"), None);
+    assert_eq!(decode("my"), Some(b"f".to_vec()));
+    assert_eq!(decode("mz"), None);
+    assert_eq!(decode("mzx"), None);
+    assert_eq!(decode("m"), None);
+  }
+}
@@ -0,0 +1,174 @@
+use std::collections::HashSet;
+
+use crate::{Permission, RoleError, RoleId, RoleRepository};
+
+// -- RoleHierarchy Structure --------------------------------------
+/// Resolves permissions inherited through role parents.
+#[derive(Debug)]
+pub struct RoleHierarchy<'a, R: RoleRepository> {
+  repo: &'a R,
+}
+
+// -- Implement Clone and Copy for RoleHierarchy without requiring R: Copy.
+impl<R: RoleRepository> Clone for RoleHierarchy<'_, R> {
+  fn clone(&self) -> Self {
+    *self
+  }
+}
+
+impl<R: RoleRepository> Copy for RoleHierarchy<'_, R> {}
+
+impl<'a, R: RoleRepository> RoleHierarchy<'a, R> {
+  /// Creates a hierarchy backed by the given repository.
+  ///
+  /// # Arguments
+  ///
+  /// * `repo` - The repository the roles are looked up in.
+  ///
+  /// # Returns
+  ///
+  /// * `Self` - The new hierarchy.
+  pub fn new(repo: &'a R) -> Self {
+    Self { repo }
+  }
+
+  /// Returns the permissions of a role together with those of its ancestors.
+  ///
+  /// # Arguments
+  ///
+  /// * `role_id` - The role to resolve.
+  ///
+  /// # Returns
+  ///
+  /// * `Result<Vec<Permission>, RoleError>` - The deduplicated permissions,
+  ///   the role's own first. Fails with `RoleError::NotFound` if the role or
+  ///   an ancestor is missing, or `RoleError::Cycle` if the parents loop.
+  pub fn effective_permissions(
+    &self,
+    role_id: &RoleId,
+  ) -> Result<Vec<Permission>, RoleError> {
+    let mut permissions = Vec::new();
+    let mut visited = HashSet::new();
+    let mut current = Some(*role_id);
+    while let Some(id) = current {
+      if !visited.insert(id) {
+        return Err(RoleError::Cycle(id));
+      }
+      let role = self.repo.get(&id).ok_or(RoleError::NotFound(id))?;
+      for permission in role.permissions() {
+        if !permissions.contains(permission) {
+          permissions.push(permission.clone());
+        }
+      }
+      current = role.parent();
+    }
+    Ok(permissions)
+  }
+}
+
+// -- Tests ------------------------------------------------------------------
+#[cfg(test)]
+mod tests {
+  use crate::{InMemoryRoleRepository, Role, RoleName};
+
+  use super::*;
+
+  fn names(permissions: &[Permission]) -> Vec<String> {
+    permissions.iter().map(Permission::to_string).collect()
+  }
+
+  #[test]
+  fn single_level_inheritance() -> Result<(), RoleError> {
+    let editor =
+      Role::new(RoleName::try_from("editor")?).with_permissions_str("read")?;
+    let admin = Role::new(RoleName::try_from("admin")?)
+      .with_permissions_str("read, delete")?
+      .with_parent(editor.id());
+    let mut repo = InMemoryRoleRepository::new();
+    repo.insert(editor.clone());
+    repo.insert(admin.clone());
+
+    let hierarchy = RoleHierarchy::new(&repo);
+    assert_eq!(
+      names(&hierarchy.effective_permissions(&admin.id())?),
+      ["read", "delete"]
+    );
+    assert_eq!(
+      names(&hierarchy.effective_permissions(&editor.id())?),
+      ["read"]
+    );
+    Ok(())
+  }
+
+  #[test]
+  fn multi_level_inheritance() -> Result<(), RoleError> {
+    let viewer =
+      Role::new(RoleName::try_from("viewer")?).with_permissions_str("read")?;
+    let editor = Role::new(RoleName::try_from("editor")?)
+      .with_permissions_str("write")?
+      .with_parent(viewer.id());
+    let admin = Role::new(RoleName::try_from("admin")?)
+      .with_permissions_str("delete")?
+      .with_parent(editor.id());
+    let mut repo = InMemoryRoleRepository::new();
+    repo.insert(viewer);
+    repo.insert(editor);
+    repo.insert(admin.clone());
+
+    let hierarchy = RoleHierarchy::new(&repo);
+    assert_eq!(
+      names(&hierarchy.effective_permissions(&admin.id())?),
+      ["delete", "write", "read"]
+    );
+    Ok(())
+  }
+
+  #[test]
+  fn cycle_is_rejected() -> Result<(), RoleError> {
+    let mut first = Role::new(RoleName::try_from("first")?);
+    let second =
+      Role::new(RoleName::try_from("second")?).with_parent(first.id());
+    first.set_parent(Some(second.id()));
+    let mut repo = InMemoryRoleRepository::new();
+    repo.insert(first.clone());
+    repo.insert(second);
+
+    let hierarchy = RoleHierarchy::new(&repo);
+    assert_eq!(
+      hierarchy.effective_permissions(&first.id()),
+      Err(RoleError::Cycle(first.id()))
+    );
+    Ok(())
+  }
+
+  #[test]
+  fn missing_role_is_rejected() -> Result<(), RoleError> {
+    let missing = RoleId::new();
+    let orphan = Role::new(RoleName::try_from("orphan")?).with_parent(missing);
+    let mut repo = InMemoryRoleRepository::new();
+    repo.insert(orphan.clone());
+
+    let hierarchy = RoleHierarchy::new(&repo);
+    assert_eq!(
+      hierarchy.effective_permissions(&orphan.id()),
+      Err(RoleError::NotFound(missing))
+    );
+    Ok(())
+  }
+
+  #[test]
+  fn hierarchy_is_copy() -> Result<(), RoleError> {
+    let viewer =
+      Role::new(RoleName::try_from("viewer")?).with_permissions_str("read")?;
+    let mut repo = InMemoryRoleRepository::new();
+    repo.insert(viewer.clone());
+
+    let hierarchy = RoleHierarchy::new(&repo);
+    let copy = hierarchy;
+    assert_eq!(
+      hierarchy.effective_permissions(&viewer.id())?,
+      copy.effective_permissions(&viewer.id())?
+    );
+    Ok(())
+  }
+}
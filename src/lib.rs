@@ -1,20 +1,34 @@
+//! Users, roles and permissions for applications that need account
+//! management.
+//!
+//! Passwords are hashed with Argon2, and roles can be looked up through the
+//! pluggable `RoleRepository` and `UserRepository` traits. Most consumers
+//! only need `use user_lib::prelude::*;`.
+#![deny(missing_docs)]
+
+mod base32;
+mod hierarchy;
+pub mod prelude;
+mod repository;
 mod role;
+#[cfg(feature = "zeroize")]
+mod secret;
 mod user;
 
-pub use role::{Role, RoleId, RoleName};
-pub use user::{User, UserError, UserId, UserName, UserPassword, UserRole};
-
-pub fn add(left: u64, right: u64) -> u64 {
-  left + right
-}
-
-#[cfg(test)]
-mod tests {
-  use super::*;
-
-  #[test]
-  fn it_works() {
-    let result = add(2, 2);
-    assert_eq!(result, 4);
-  }
-}
+pub use hierarchy::RoleHierarchy;
+pub use repository::{
+  InMemoryRoleRepository, InMemoryUserRepository, RoleRepository,
+  UserRepository,
+};
+pub use role::{
+  Permission, Role, RoleBuilder, RoleError, RoleId, RoleName, RoleSet,
+  empty_roles,
+};
+#[cfg(feature = "zeroize")]
+pub use secret::SecretPassword;
+pub use user::{
+  CreationConstraints, User, UserError, UserId, UserName, UserPassword,
+  UserPublic, UserRole, UserRoleSnapshot, UserSnapshot, account_age_extremes,
+  can_create_user, gained_privileged_role, paginate_users, relative_cost,
+  roles_by_usage,
+};
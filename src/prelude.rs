@@ -0,0 +1,28 @@
+//! Re-exports the commonly used types of the crate.
+//!
+//! ```
+//! use user_lib::prelude::*;
+//!
+//! # fn main() -> Result<(), Box<dyn std::error::Error>> {
+//! let admin = Role::builder(RoleName::try_from("admin")?)
+//!   .permission(Permission::try_from("users:write")?)
+//!   .build();
+//! let mut roles = InMemoryRoleRepository::new();
+//! roles.insert(admin.clone());
+//!
+//! let user = User::new(
+//!   UserName::new("bob"),
+//!   UserPassword::new("password", "password")?,
+//! )?
+//! .with_role(admin.id());
+//! assert_eq!(user.resolve_roles(&roles), vec![admin]);
+//! # Ok(())
+//! # }
+//! ```
+
+pub use crate::{
+  InMemoryRoleRepository, InMemoryUserRepository, Permission, Role,
+  RoleBuilder, RoleError, RoleHierarchy, RoleId, RoleName, RoleRepository,
+  RoleSet, User, UserError, UserId, UserName, UserPassword, UserRepository,
+  UserRole,
+};
@@ -0,0 +1,313 @@
+use std::collections::HashMap;
+
+use crate::{Role, RoleId, User, UserError, UserId, UserName};
+
+// -- RoleRepository Trait -----------------------------------------
+/// Storage abstraction for roles.
+pub trait RoleRepository {
+  /// Returns the role with the given ID, if it exists.
+  ///
+  /// # Arguments
+  ///
+  /// * `id` - The ID of the role to look up.
+  ///
+  /// # Returns
+  ///
+  /// * `Option<Role>` - The role, or `None` if it is not stored.
+  fn get(&self, id: &RoleId) -> Option<Role>;
+
+  /// Stores a role, replacing any role with the same ID.
+  ///
+  /// # Arguments
+  ///
+  /// * `role` - The role to store.
+  fn insert(&mut self, role: Role);
+
+  /// Removes the role with the given ID.
+  ///
+  /// # Arguments
+  ///
+  /// * `id` - The ID of the role to remove.
+  ///
+  /// # Returns
+  ///
+  /// * `Option<Role>` - The removed role, or `None` if it was not stored.
+  fn remove(&mut self, id: &RoleId) -> Option<Role>;
+
+  /// Returns every stored role.
+  ///
+  /// # Returns
+  ///
+  /// * `Vec<Role>` - All stored roles.
+  fn all(&self) -> Vec<Role>;
+}
+
+// -- InMemoryRoleRepository Structure -----------------------------
+/// A `RoleRepository` backed by a `HashMap`.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct InMemoryRoleRepository {
+  roles: HashMap<RoleId, Role>,
+}
+
+impl InMemoryRoleRepository {
+  /// Creates a new, empty repository.
+  ///
+  /// # Returns
+  ///
+  /// * `Self` - The new repository.
+  pub fn new() -> Self {
+    Self::default()
+  }
+}
+
+impl RoleRepository for InMemoryRoleRepository {
+  fn get(&self, id: &RoleId) -> Option<Role> {
+    self.roles.get(id).cloned()
+  }
+
+  fn insert(&mut self, role: Role) {
+    self.roles.insert(role.id(), role);
+  }
+
+  fn remove(&mut self, id: &RoleId) -> Option<Role> {
+    self.roles.remove(id)
+  }
+
+  fn all(&self) -> Vec<Role> {
+    self.roles.values().cloned().collect()
+  }
+}
+
+// -- UserRepository Trait -----------------------------------------
+/// Storage abstraction for users.
+pub trait UserRepository {
+  /// Returns the user with the given ID, if it exists.
+  ///
+  /// # Arguments
+  ///
+  /// * `id` - The ID of the user to look up.
+  ///
+  /// # Returns
+  ///
+  /// * `Option<User>` - The user, or `None` if it is not stored.
+  fn find_by_id(&self, id: &UserId) -> Option<User>;
+
+  /// Returns the user with the given name, if it exists.
+  ///
+  /// Names are matched like `UserName::eq_ignore_case`, ignoring case and
+  /// surrounding whitespace.
+  ///
+  /// # Arguments
+  ///
+  /// * `name` - The name of the user to look up.
+  ///
+  /// # Returns
+  ///
+  /// * `Option<User>` - The user, or `None` if no user has that name.
+  fn find_by_name(&self, name: &UserName) -> Option<User>;
+
+  /// Stores a user, replacing any user with the same ID.
+  ///
+  /// # Arguments
+  ///
+  /// * `user` - The user to store.
+  ///
+  /// # Returns
+  ///
+  /// * `Result<(), UserError>` - `UserError::NameTaken` if a different user
+  ///   already has the same name, ignoring case, in which case nothing is
+  ///   stored.
+  fn upsert(&mut self, user: User) -> Result<(), UserError>;
+
+  /// Removes the user with the given ID.
+  ///
+  /// # Arguments
+  ///
+  /// * `id` - The ID of the user to remove.
+  ///
+  /// # Returns
+  ///
+  /// * `bool` - `true` if a user was removed.
+  fn delete(&mut self, id: &UserId) -> bool;
+}
+
+// -- InMemoryUserRepository Structure -----------------------------
+/// A `UserRepository` backed by a `HashMap`, with a secondary index on
+/// normalized user names.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct InMemoryUserRepository {
+  users: HashMap<UserId, User>,
+  names: HashMap<String, UserId>,
+}
+
+impl InMemoryUserRepository {
+  /// Creates a new, empty repository.
+  ///
+  /// # Returns
+  ///
+  /// * `Self` - The new repository.
+  pub fn new() -> Self {
+    Self::default()
+  }
+}
+
+impl UserRepository for InMemoryUserRepository {
+  fn find_by_id(&self, id: &UserId) -> Option<User> {
+    self.users.get(id).cloned()
+  }
+
+  fn find_by_name(&self, name: &UserName) -> Option<User> {
+    self
+      .names
+      .get(&name.normalized())
+      .and_then(|id| self.find_by_id(id))
+  }
+
+  fn upsert(&mut self, user: User) -> Result<(), UserError> {
+    let key = user.name().normalized();
+    if self
+      .names
+      .get(&key)
+      .is_some_and(|holder| *holder != user.id())
+    {
+      return Err(UserError::NameTaken);
+    }
+    if let Some(previous) = self.users.get(&user.id()) {
+      self.names.remove(&previous.name().normalized());
+    }
+    self.names.insert(key, user.id());
+    self.users.insert(user.id(), user);
+    Ok(())
+  }
+
+  fn delete(&mut self, id: &UserId) -> bool {
+    match self.users.remove(id) {
+      Some(user) => {
+        self.names.remove(&user.name().normalized());
+        true
+      }
+      None => false,
+    }
+  }
+}
+
+// -- Tests ------------------------------------------------------------------
+#[cfg(test)]
+mod tests {
+  use crate::{RoleName, UserPassword};
+
+  use super::*;
+
+  #[test]
+  fn insert_and_get_role() -> Result<(), Box<dyn std::error::Error>> {
+    let role = Role::new(RoleName::try_from("admin")?);
+    let mut repo = InMemoryRoleRepository::new();
+    repo.insert(role.clone());
+    assert_eq!(repo.get(&role.id()), Some(role));
+    assert_eq!(repo.get(&RoleId::new()), None);
+    Ok(())
+  }
+
+  #[test]
+  fn remove_role() -> Result<(), Box<dyn std::error::Error>> {
+    let role = Role::new(RoleName::try_from("admin")?);
+    let mut repo = InMemoryRoleRepository::new();
+    repo.insert(role.clone());
+    assert_eq!(repo.remove(&role.id()), Some(role.clone()));
+    assert_eq!(repo.get(&role.id()), None);
+    assert_eq!(repo.remove(&role.id()), None);
+    Ok(())
+  }
+
+  #[test]
+  fn list_all_roles() -> Result<(), Box<dyn std::error::Error>> {
+    let admin = Role::new(RoleName::try_from("admin")?);
+    let editor = Role::new(RoleName::try_from("editor")?);
+    let mut repo = InMemoryRoleRepository::new();
+    repo.insert(admin.clone());
+    repo.insert(editor.clone());
+
+    let mut all = repo.all();
+    all.sort();
+    let mut expected = vec![admin, editor];
+    expected.sort();
+    assert_eq!(all, expected);
+    Ok(())
+  }
+
+  #[test]
+  fn find_user_by_name_after_rename() -> Result<(), Box<dyn std::error::Error>>
+  {
+    let mut user = User::new(
+      UserName::new("bob"),
+      UserPassword::new("password", "password")?,
+    )?;
+    let mut repo = InMemoryUserRepository::new();
+    repo.upsert(user.clone())?;
+    assert_eq!(repo.find_by_name(&UserName::new("bob")), Some(user.clone()));
+
+    user.rename(UserName::new("robert"));
+    repo.upsert(user.clone())?;
+    assert_eq!(
+      repo.find_by_name(&UserName::new("robert")),
+      Some(user.clone())
+    );
+    assert_eq!(repo.find_by_name(&UserName::new("bob")), None);
+    assert_eq!(repo.find_by_id(&user.id()), Some(user));
+    Ok(())
+  }
+
+  #[test]
+  fn delete_user_cleans_name_index() -> Result<(), Box<dyn std::error::Error>> {
+    let user = User::new(
+      UserName::new("bob"),
+      UserPassword::new("password", "password")?,
+    )?;
+    let mut repo = InMemoryUserRepository::new();
+    repo.upsert(user.clone())?;
+
+    assert!(repo.delete(&user.id()));
+    assert_eq!(repo.find_by_id(&user.id()), None);
+    assert_eq!(repo.find_by_name(&UserName::new("bob")), None);
+    assert!(!repo.delete(&user.id()));
+    Ok(())
+  }
+
+  #[test]
+  fn reject_duplicate_user_name() -> Result<(), Box<dyn std::error::Error>> {
+    let first = User::new(
+      UserName::new("bob"),
+      UserPassword::new("password", "password")?,
+    )?;
+    let second = User::new(
+      UserName::new("bob"),
+      UserPassword::new("password", "password")?,
+    )?;
+    let mut repo = InMemoryUserRepository::new();
+    repo.upsert(first.clone())?;
+    assert_eq!(repo.upsert(second.clone()), Err(UserError::NameTaken));
+    assert_eq!(repo.find_by_id(&second.id()), None);
+
+    assert!(!repo.delete(&second.id()));
+    assert_eq!(repo.find_by_name(&UserName::new("bob")), Some(first));
+    Ok(())
+  }
+
+  #[test]
+  fn user_names_are_matched_ignoring_case()
+  -> Result<(), Box<dyn std::error::Error>> {
+    let bob = User::new(
+      UserName::new("Bob"),
+      UserPassword::new("password", "password")?,
+    )?;
+    let other = User::new(
+      UserName::new("bob"),
+      UserPassword::new("password", "password")?,
+    )?;
+    let mut repo = InMemoryUserRepository::new();
+    repo.upsert(bob.clone())?;
+    assert_eq!(repo.upsert(other), Err(UserError::NameTaken));
+    assert_eq!(repo.find_by_name(&UserName::new(" BOB ")), Some(bob));
+    Ok(())
+  }
+}
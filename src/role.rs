@@ -1,6 +1,7 @@
-use std::{error::Error, str::FromStr};
+use std::{collections::BTreeSet, str::FromStr};
 
 use serde::{Deserialize, Serialize};
+use unicode_normalization::UnicodeNormalization;
 
 // -- RoleId Structure ---------------------------------------------
 /// Represents a unique identifier for a role.
@@ -16,6 +17,7 @@ use serde::{Deserialize, Serialize};
   Serialize,
   Deserialize,
 )]
+#[cfg_attr(feature = "sqlx", derive(sqlx::Type), sqlx(transparent))]
 pub struct RoleId(uuid::Uuid);
 
 // -- Default Implementation for RoleId.
@@ -37,6 +39,97 @@ impl RoleId {
   pub fn new() -> Self {
     Self(uuid::Uuid::new_v4())
   }
+
+  /// Returns the nil role ID.
+  ///
+  /// # Returns
+  ///
+  /// The role ID with all bits set to zero.
+  pub const fn nil() -> Self {
+    Self(uuid::Uuid::nil())
+  }
+
+  /// Creates a role ID from a 128-bit integer.
+  ///
+  /// This is useful for minting predictable IDs in tests.
+  ///
+  /// # Returns
+  ///
+  /// The role ID with the integer's bits.
+  pub const fn from_u128(value: u128) -> Self {
+    Self(uuid::Uuid::from_u128(value))
+  }
+
+  /// Returns the underlying UUID of the role ID.
+  ///
+  /// # Returns
+  ///
+  /// The wrapped UUID.
+  pub fn as_uuid(&self) -> uuid::Uuid {
+    self.0
+  }
+}
+
+// -- Implements From<uuid::Uuid> for RoleId
+impl From<uuid::Uuid> for RoleId {
+  fn from(value: uuid::Uuid) -> Self {
+    Self(value)
+  }
+}
+
+// -- RoleError Enum ---------------------------------------------
+/// Represents an error raised while validating role data.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[non_exhaustive]
+pub enum RoleError {
+  /// The role name was empty.
+  Empty,
+  /// The role name was shorter than allowed.
+  TooShort {
+    /// The minimum allowed length.
+    min: usize,
+    /// The length of the rejected name.
+    actual: usize,
+  },
+  /// The role name was longer than allowed.
+  TooLong {
+    /// The maximum allowed length.
+    max: usize,
+    /// The length of the rejected name.
+    actual: usize,
+  },
+  /// A permission was not valid; carries the rejected value.
+  InvalidPermission(String),
+  /// The role could not be found.
+  NotFound(RoleId),
+  /// The role's parents loop back to the role.
+  Cycle(RoleId),
+}
+
+impl std::error::Error for RoleError {}
+
+// -- Display Implementation for RoleError
+impl std::fmt::Display for RoleError {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    match self {
+      RoleError::Empty => write!(f, "Role name cannot be empty"),
+      RoleError::TooShort { min, actual } => write!(
+        f,
+        "Role name must be at least {min} characters long (got {actual})"
+      ),
+      RoleError::TooLong { max, actual } => write!(
+        f,
+        "Role name must be at most {max} characters long (got {actual})"
+      ),
+      RoleError::InvalidPermission(value) => {
+        write!(f, "Invalid permission: {value:?}")
+      }
+      RoleError::NotFound(role_id) => write!(f, "Role {role_id} not found"),
+      RoleError::Cycle(role_id) => {
+        write!(f, "Role {role_id} is part of a parent cycle")
+      }
+    }
+  }
 }
 
 // -- RoleName Structure ---------------------------------------------
@@ -44,8 +137,26 @@ impl RoleId {
 #[derive(
   Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize,
 )]
+#[serde(try_from = "String")]
 pub struct RoleName(std::rc::Rc<str>);
 
+impl RoleName {
+  /// The minimum length of a role name, in characters.
+  pub const MIN_LENGTH: usize = 3;
+
+  /// The maximum length of a role name, in characters.
+  pub const MAX_LENGTH: usize = 64;
+
+  /// Returns the role name as a string slice.
+  ///
+  /// # Returns
+  ///
+  /// The borrowed name, without allocating.
+  pub fn as_str(&self) -> &str {
+    &self.0
+  }
+}
+
 // -- Implements Display for RoleName
 impl std::fmt::Display for RoleName {
   fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
@@ -53,23 +164,108 @@ impl std::fmt::Display for RoleName {
   }
 }
 
+// -- Implements PartialEq<str> for RoleName
+impl PartialEq<str> for RoleName {
+  fn eq(&self, other: &str) -> bool {
+    &*self.0 == other
+  }
+}
+
+// -- Implements PartialEq<&str> for RoleName
+impl PartialEq<&str> for RoleName {
+  fn eq(&self, other: &&str) -> bool {
+    &*self.0 == *other
+  }
+}
+
 // -- Implements TryFrom<&str> for RoleName
 impl TryFrom<&str> for RoleName {
-  type Error = Box<dyn Error>;
+  type Error = RoleError;
 
   fn try_from(value: &str) -> Result<Self, Self::Error> {
+    let value: String = value.nfc().collect();
+    let length = value.chars().count();
     if value.is_empty() {
-      Err("Role name cannot be empty".into())
-    } else if value.len() < 3 {
-      Err("Role name must be at least 3 characters long".into())
+      Err(RoleError::Empty)
+    } else if length < RoleName::MIN_LENGTH {
+      Err(RoleError::TooShort {
+        min: RoleName::MIN_LENGTH,
+        actual: length,
+      })
+    } else if length > RoleName::MAX_LENGTH {
+      Err(RoleError::TooLong {
+        max: RoleName::MAX_LENGTH,
+        actual: length,
+      })
     } else {
       Ok(Self(std::rc::Rc::from(value)))
     }
   }
 }
 
+// -- Implements TryFrom<String> for RoleName, so deserialized names are
+// validated too
+impl TryFrom<String> for RoleName {
+  type Error = RoleError;
+
+  fn try_from(value: String) -> Result<Self, Self::Error> {
+    Self::try_from(value.as_str())
+  }
+}
+
 impl FromStr for RoleName {
-  type Err = Box<dyn Error>;
+  type Err = RoleError;
+
+  fn from_str(s: &str) -> Result<Self, Self::Err> {
+    Self::try_from(s)
+  }
+}
+
+// -- Permission Structure ---------------------------------------------
+/// Represents a permission granted by a role, such as `read` or
+/// `users:write`.
+#[derive(
+  Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize,
+)]
+#[serde(try_from = "String")]
+pub struct Permission(std::rc::Rc<str>);
+
+// -- Implements Display for Permission
+impl std::fmt::Display for Permission {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    write!(f, "{}", self.0)
+  }
+}
+
+// -- Implements TryFrom<&str> for Permission
+impl TryFrom<&str> for Permission {
+  type Error = RoleError;
+
+  fn try_from(value: &str) -> Result<Self, Self::Error> {
+    let valid = !value.is_empty()
+      && value.chars().all(|c| {
+        c.is_ascii_alphanumeric() || matches!(c, '_' | '-' | ':' | '.')
+      });
+    if valid {
+      Ok(Self(std::rc::Rc::from(value)))
+    } else {
+      Err(RoleError::InvalidPermission(value.to_string()))
+    }
+  }
+}
+
+// -- Implements TryFrom<String> for Permission, so deserialized permissions
+// are validated too
+impl TryFrom<String> for Permission {
+  type Error = RoleError;
+
+  fn try_from(value: String) -> Result<Self, Self::Error> {
+    Self::try_from(value.as_str())
+  }
+}
+
+impl FromStr for Permission {
+  type Err = RoleError;
 
   fn from_str(s: &str) -> Result<Self, Self::Err> {
     Self::try_from(s)
@@ -84,14 +280,74 @@ impl FromStr for RoleName {
 pub struct Role {
   id: RoleId,
   name: RoleName,
+  #[serde(default)]
+  description: Option<String>,
+  #[serde(default)]
+  parent: Option<RoleId>,
+  #[serde(default)]
+  permissions: Vec<Permission>,
 }
 
 // -- Implement Role
 impl Role {
+  /// Creates a new role with no description or permissions.
+  ///
+  /// # Returns
+  ///
+  /// The new role.
   pub fn new(name: RoleName) -> Self {
     Self {
       id: RoleId::new(),
       name,
+      description: None,
+      parent: None,
+      permissions: Vec::new(),
+    }
+  }
+
+  /// Starts building a role with the given name.
+  ///
+  /// # Returns
+  ///
+  /// A builder for the role.
+  pub fn builder(name: RoleName) -> RoleBuilder {
+    RoleBuilder {
+      role: Self::new(name),
+    }
+  }
+
+  /// Adds permissions parsed from a comma-separated list, such as
+  /// `"read, write, delete"`.
+  ///
+  /// Whitespace around each permission is trimmed, empty entries are skipped
+  /// and permissions the role already has are not added again.
+  ///
+  /// # Returns
+  ///
+  /// The updated role, or `RoleError::InvalidPermission` if any entry is not
+  /// a valid permission. On error no permissions are added.
+  pub fn with_permissions_str(
+    mut self,
+    value: &str,
+  ) -> Result<Self, RoleError> {
+    let parsed = value
+      .split(',')
+      .map(str::trim)
+      .filter(|token| !token.is_empty())
+      .map(Permission::try_from)
+      .collect::<Result<Vec<_>, _>>()?;
+    self.push_unique_permissions(parsed);
+    Ok(self)
+  }
+
+  fn push_unique_permissions(
+    &mut self,
+    permissions: impl IntoIterator<Item = Permission>,
+  ) {
+    for permission in permissions {
+      if !self.permissions.contains(&permission) {
+        self.permissions.push(permission);
+      }
     }
   }
 
@@ -112,4 +368,432 @@ impl Role {
   pub fn name(&self) -> &RoleName {
     &self.name
   }
+
+  /// Renames the role.
+  ///
+  /// # Returns
+  ///
+  /// A mutable reference to the role.
+  pub fn rename(&mut self, new: RoleName) -> &mut Self {
+    self.name = new;
+    self
+  }
+
+  /// Sets the parent role, whose permissions this role inherits.
+  ///
+  /// # Returns
+  ///
+  /// The updated role.
+  pub fn with_parent(mut self, parent: RoleId) -> Self {
+    self.parent = Some(parent);
+    self
+  }
+
+  /// Sets or clears the parent role.
+  ///
+  /// # Returns
+  ///
+  /// A mutable reference to the role.
+  pub fn set_parent(&mut self, parent: Option<RoleId>) -> &mut Self {
+    self.parent = parent;
+    self
+  }
+
+  /// Returns the parent role, if any.
+  ///
+  /// # Returns
+  ///
+  /// The ID of the parent role.
+  pub fn parent(&self) -> Option<RoleId> {
+    self.parent
+  }
+
+  /// Returns the description of the role.
+  ///
+  /// # Returns
+  ///
+  /// The description, if one was set.
+  pub fn description(&self) -> Option<&str> {
+    self.description.as_deref()
+  }
+
+  /// Returns the permissions granted by the role.
+  ///
+  /// # Returns
+  ///
+  /// The permissions of the role.
+  pub fn permissions(&self) -> &[Permission] {
+    &self.permissions
+  }
+}
+
+// -- RoleBuilder Structure ---------------------------------------------
+/// Builds a `Role` step by step.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RoleBuilder {
+  role: Role,
+}
+
+impl RoleBuilder {
+  /// Sets the description of the role.
+  ///
+  /// # Returns
+  ///
+  /// The updated builder.
+  pub fn description(mut self, description: impl Into<String>) -> Self {
+    self.role.description = Some(description.into());
+    self
+  }
+
+  /// Sets the parent role, whose permissions the role inherits.
+  ///
+  /// # Returns
+  ///
+  /// The updated builder.
+  pub fn parent(mut self, parent: RoleId) -> Self {
+    self.role.parent = Some(parent);
+    self
+  }
+
+  /// Adds a permission to the role.
+  ///
+  /// # Returns
+  ///
+  /// The updated builder.
+  pub fn permission(mut self, permission: Permission) -> Self {
+    self.role.push_unique_permissions([permission]);
+    self
+  }
+
+  /// Adds several permissions to the role.
+  ///
+  /// # Returns
+  ///
+  /// The updated builder.
+  pub fn permissions(
+    mut self,
+    permissions: impl IntoIterator<Item = Permission>,
+  ) -> Self {
+    self.role.push_unique_permissions(permissions);
+    self
+  }
+
+  /// Finishes building the role.
+  ///
+  /// # Returns
+  ///
+  /// The built role.
+  pub fn build(self) -> Role {
+    self.role
+  }
+}
+
+/// Finds the roles that grant no permissions.
+///
+/// # Returns
+///
+/// The roles with an empty permission list.
+pub fn empty_roles(roles: &[Role]) -> Vec<&Role> {
+  roles
+    .iter()
+    .filter(|role| role.permissions.is_empty())
+    .collect()
+}
+
+// -- RoleSet Structure ---------------------------------------------
+/// Represents a deduplicated set of role IDs.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RoleSet(BTreeSet<RoleId>);
+
+// -- Implements FromIterator<RoleId> for RoleSet
+impl FromIterator<RoleId> for RoleSet {
+  fn from_iter<I: IntoIterator<Item = RoleId>>(iter: I) -> Self {
+    Self(iter.into_iter().collect())
+  }
+}
+
+impl RoleSet {
+  /// Creates a new, empty `RoleSet`.
+  ///
+  /// # Returns
+  ///
+  /// The new, empty set.
+  pub fn new() -> Self {
+    Self::default()
+  }
+
+  /// Returns whether the set contains the given role.
+  ///
+  /// # Returns
+  ///
+  /// `true` if the role is in the set.
+  pub fn contains(&self, role_id: &RoleId) -> bool {
+    self.0.contains(role_id)
+  }
+
+  /// Returns whether the set contains any of the given roles.
+  ///
+  /// # Returns
+  ///
+  /// `true` if at least one of the roles is in the set.
+  pub fn intersects(&self, role_ids: &[RoleId]) -> bool {
+    role_ids.iter().any(|id| self.contains(id))
+  }
+
+  /// Returns the roles that are in either set.
+  ///
+  /// # Returns
+  ///
+  /// A new set containing the union of both sets.
+  pub fn union(&self, other: &RoleSet) -> RoleSet {
+    Self(self.0.union(&other.0).copied().collect())
+  }
+
+  /// Returns the roles that are in this set but not in `other`.
+  ///
+  /// # Returns
+  ///
+  /// A new set containing the difference of both sets.
+  pub fn difference(&self, other: &RoleSet) -> RoleSet {
+    Self(self.0.difference(&other.0).copied().collect())
+  }
+
+  /// Returns the number of roles in the set.
+  ///
+  /// # Returns
+  ///
+  /// The number of roles in the set.
+  pub fn len(&self) -> usize {
+    self.0.len()
+  }
+
+  /// Returns whether the set is empty.
+  ///
+  /// # Returns
+  ///
+  /// `true` if the set contains no roles.
+  pub fn is_empty(&self) -> bool {
+    self.0.is_empty()
+  }
+
+  /// Returns an iterator over the roles in the set.
+  ///
+  /// # Returns
+  ///
+  /// An iterator over the role IDs, in ascending order.
+  pub fn iter(&self) -> impl Iterator<Item = &RoleId> {
+    self.0.iter()
+  }
+}
+
+// -- Tests ------------------------------------------------------------------
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn role_id_uuid_round_trip() {
+    let uuid = uuid::Uuid::new_v4();
+    let id = RoleId::from(uuid);
+    assert_eq!(id.as_uuid(), uuid);
+  }
+
+  #[test]
+  fn role_id_from_u128_is_stable() {
+    assert_eq!(RoleId::from_u128(1), RoleId::from_u128(1));
+    assert_eq!(RoleId::from_u128(0), RoleId::nil());
+  }
+
+  #[cfg(feature = "sqlx")]
+  #[test]
+  fn bind_role_id_in_query() {
+    let mut query = sqlx::QueryBuilder::<sqlx::Postgres>::new(
+      "SELECT * FROM roles WHERE id = ",
+    );
+    query.push_bind(RoleId::from_u128(1));
+    assert_eq!(query.sql(), "SELECT * FROM roles WHERE id = $1");
+  }
+
+  #[test]
+  fn empty_role_name_is_rejected() {
+    match RoleName::try_from("") {
+      Err(RoleError::Empty) => {}
+      other => panic!("expected RoleError::Empty, got {other:?}"),
+    }
+  }
+
+  #[test]
+  fn short_role_name_is_rejected() {
+    match "ab".parse::<RoleName>() {
+      Err(RoleError::TooShort { min, actual }) => {
+        assert_eq!(min, 3);
+        assert_eq!(actual, 2);
+      }
+      other => panic!("expected RoleError::TooShort, got {other:?}"),
+    }
+  }
+
+  #[test]
+  fn role_name_at_max_length_is_accepted() {
+    let name = "a".repeat(RoleName::MAX_LENGTH);
+    assert!(RoleName::try_from(name.as_str()).is_ok());
+  }
+
+  #[test]
+  fn long_role_name_is_rejected() {
+    let name = "a".repeat(RoleName::MAX_LENGTH + 1);
+    match RoleName::try_from(name.as_str()) {
+      Err(RoleError::TooLong { max, actual }) => {
+        assert_eq!(max, 64);
+        assert_eq!(actual, 65);
+      }
+      other => panic!("expected RoleError::TooLong, got {other:?}"),
+    }
+  }
+
+  #[test]
+  fn role_name_length_counts_characters() {
+    let name = "\u{7ba1}".repeat(RoleName::MAX_LENGTH);
+    assert!(RoleName::try_from(name.as_str()).is_ok());
+    match RoleName::try_from("\u{7ba1}\u{7406}") {
+      Err(RoleError::TooShort { actual, .. }) => assert_eq!(actual, 2),
+      other => panic!("expected RoleError::TooShort, got {other:?}"),
+    }
+  }
+
+  #[test]
+  fn role_name_nfc_normalization() -> Result<(), RoleError> {
+    let composed = RoleName::try_from("caf\u{e9}")?;
+    let decomposed = RoleName::try_from("cafe\u{301}")?;
+    assert_eq!(composed, decomposed);
+    Ok(())
+  }
+
+  #[test]
+  fn deserialized_role_name_is_validated()
+  -> Result<(), Box<dyn std::error::Error>> {
+    let name: RoleName = serde_json::from_str("\"cafe\u{301}\"")?;
+    assert_eq!(name, RoleName::try_from("caf\u{e9}")?);
+
+    let long = format!("\"{}\"", "a".repeat(RoleName::MAX_LENGTH + 1));
+    let error = serde_json::from_str::<RoleName>(&long).unwrap_err();
+    assert!(error.to_string().contains("at most 64 characters"));
+    Ok(())
+  }
+
+  #[test]
+  fn role_name_as_str() -> Result<(), RoleError> {
+    assert_eq!(RoleName::try_from("admin")?.as_str(), "admin");
+    Ok(())
+  }
+
+  #[test]
+  fn compare_role_name_with_str() -> Result<(), RoleError> {
+    let name = RoleName::try_from("admin")?;
+    assert!(name == "admin");
+    assert!(name == *"admin");
+    assert!(name != "editor");
+    Ok(())
+  }
+
+  #[test]
+  fn rename_role() -> Result<(), RoleError> {
+    let mut role = Role::new(RoleName::try_from("admin")?);
+    let id = role.id();
+    role.rename(RoleName::try_from("superuser")?);
+    assert_eq!(role.name(), &RoleName::try_from("superuser")?);
+    assert_eq!(role.id(), id);
+    Ok(())
+  }
+
+  #[test]
+  fn parse_role_permissions() -> Result<(), RoleError> {
+    let role = Role::new(RoleName::try_from("editor")?)
+      .with_permissions_str("read, write , delete,,")?;
+    let permissions: Vec<String> =
+      role.permissions().iter().map(|p| p.to_string()).collect();
+    assert_eq!(permissions, vec!["read", "write", "delete"]);
+    Ok(())
+  }
+
+  #[test]
+  fn reject_malformed_permission() -> Result<(), RoleError> {
+    let result = Role::new(RoleName::try_from("editor")?)
+      .with_permissions_str("read, wr ite");
+    assert_eq!(
+      result,
+      Err(RoleError::InvalidPermission("wr ite".to_string()))
+    );
+    Ok(())
+  }
+
+  #[test]
+  fn deserialized_permission_is_validated()
+  -> Result<(), Box<dyn std::error::Error>> {
+    let read: Permission = serde_json::from_str("\"read\"")?;
+    assert_eq!(read, Permission::try_from("read")?);
+    assert!(serde_json::from_str::<Permission>("\"wr ite\"").is_err());
+    assert!(serde_json::from_str::<Permission>("\"\"").is_err());
+
+    let role = Role::new(RoleName::try_from("editor")?);
+    let mut json = serde_json::to_value(&role)?;
+    json["permissions"] = serde_json::json!(["has space", ""]);
+    assert!(serde_json::from_value::<Role>(json).is_err());
+    Ok(())
+  }
+
+  #[test]
+  fn build_role() -> Result<(), RoleError> {
+    let read = Permission::try_from("read")?;
+    let write = Permission::try_from("write")?;
+    let role = Role::builder(RoleName::try_from("admin")?)
+      .description("Full access")
+      .permission(read.clone())
+      .permissions([write.clone(), read.clone()])
+      .build();
+    assert_eq!(role.name(), &RoleName::try_from("admin")?);
+    assert_eq!(role.description(), Some("Full access"));
+    assert_eq!(role.permissions(), &[read, write]);
+    Ok(())
+  }
+
+  #[test]
+  fn find_empty_roles() -> Result<(), RoleError> {
+    let guest = Role::new(RoleName::try_from("guest")?);
+    let editor =
+      Role::new(RoleName::try_from("editor")?).with_permissions_str("read")?;
+    let roles = [guest.clone(), editor];
+    assert_eq!(empty_roles(&roles), vec![&guest]);
+    Ok(())
+  }
+
+  #[test]
+  fn role_set_intersects() {
+    let admin = RoleId::new();
+    let editor = RoleId::new();
+    let viewer = RoleId::new();
+    let set: RoleSet = [admin, editor].into_iter().collect();
+    assert!(set.intersects(&[viewer, editor]));
+    assert!(!set.intersects(&[viewer]));
+    assert!(!set.intersects(&[]));
+  }
+
+  #[test]
+  fn role_set_union_and_difference() {
+    let admin = RoleId::new();
+    let editor = RoleId::new();
+    let viewer = RoleId::new();
+    let left: RoleSet = [admin, editor, editor].into_iter().collect();
+    let right: RoleSet = [editor, viewer].into_iter().collect();
+    assert_eq!(left.len(), 2);
+
+    let union = left.union(&right);
+    assert_eq!(union.len(), 3);
+    assert!(union.contains(&admin));
+    assert!(union.contains(&editor));
+    assert!(union.contains(&viewer));
+
+    let difference = left.difference(&right);
+    assert_eq!(difference, [admin].into_iter().collect());
+  }
 }
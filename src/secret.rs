@@ -0,0 +1,103 @@
+use zeroize::{Zeroize, ZeroizeOnDrop};
+
+// -- SecretPassword Structure -------------------------------------
+/// A plaintext password whose buffer is zeroed when it is dropped.
+///
+/// `UserPassword` constructors and verifiers accept it anywhere a `&str` is
+/// accepted.
+pub struct SecretPassword(String);
+
+impl SecretPassword {
+  /// Wraps a plaintext password.
+  ///
+  /// # Arguments
+  ///
+  /// * `password` - The plaintext password.
+  ///
+  /// # Returns
+  ///
+  /// * `Self` - The wrapped password.
+  pub fn new(password: String) -> Self {
+    SecretPassword(password)
+  }
+}
+
+impl From<String> for SecretPassword {
+  fn from(value: String) -> Self {
+    SecretPassword(value)
+  }
+}
+
+impl AsRef<str> for SecretPassword {
+  fn as_ref(&self) -> &str {
+    &self.0
+  }
+}
+
+// -- Implement Debug for SecretPassword without exposing the plaintext.
+impl std::fmt::Debug for SecretPassword {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    f.write_str("SecretPassword(\"***\")")
+  }
+}
+
+impl Zeroize for SecretPassword {
+  fn zeroize(&mut self) {
+    self.0.zeroize();
+  }
+}
+
+impl Drop for SecretPassword {
+  fn drop(&mut self) {
+    self.zeroize();
+  }
+}
+
+impl ZeroizeOnDrop for SecretPassword {}
+
+// -- Tests ------------------------------------------------------------------
+#[cfg(test)]
+mod tests {
+  use crate::UserPassword;
+
+  use super::*;
+
+  /// Reads `len` bytes starting at `ptr`.
+  ///
+  /// # Safety
+  ///
+  /// The memory must still be allocated.
+  unsafe fn inspect(ptr: *const u8, len: usize) -> Vec<u8> {
+    unsafe { std::slice::from_raw_parts(ptr, len) }.to_vec()
+  }
+
+  #[test]
+  fn zeroize_clears_buffer() {
+    // Dropping also frees the buffer, so reading it afterwards would be
+    // undefined behaviour. Drop delegates to `zeroize`, which keeps the
+    // allocation alive and can be inspected safely.
+    let mut secret = SecretPassword::new(String::from("hunter2"));
+    let ptr = secret.0.as_ptr();
+    let capacity = secret.0.capacity();
+    assert_eq!(unsafe { inspect(ptr, 7) }, b"hunter2");
+
+    secret.zeroize();
+    assert!(secret.0.is_empty());
+    assert!(unsafe { inspect(ptr, capacity) }.iter().all(|&b| b == 0));
+  }
+
+  #[test]
+  fn hash_and_verify_secret_password() -> Result<(), Box<dyn std::error::Error>>
+  {
+    let password = UserPassword::new(
+      SecretPassword::from(String::from("password")),
+      SecretPassword::from(String::from("password")),
+    )?;
+    assert!(password.verify(SecretPassword::from(String::from("password")))?);
+    assert_eq!(
+      format!("{:?}", SecretPassword::from(String::from("password"))),
+      "SecretPassword(\"***\")"
+    );
+    Ok(())
+  }
+}
@@ -1,4 +1,4 @@
-use std::process::exit;
+use std::collections::HashSet;
 
 use argon2::{
   Argon2, PasswordHash, PasswordVerifier,
@@ -6,8 +6,10 @@ use argon2::{
 };
 
 use serde::{Deserialize, Serialize};
+use subtle::ConstantTimeEq;
+use unicode_normalization::UnicodeNormalization;
 
-use crate::RoleId;
+use crate::{Role, RoleId, RoleRepository, RoleSet, base32};
 
 // -- User ID Structure --------------------------------------------
 /// Represents a user's ID
@@ -23,6 +25,7 @@ use crate::RoleId;
   Serialize,
   Deserialize,
 )]
+#[cfg_attr(feature = "sqlx", derive(sqlx::Type), sqlx(transparent))]
 pub struct UserId(uuid::Uuid);
 
 impl Default for UserId {
@@ -46,6 +49,62 @@ impl UserId {
   pub fn new() -> Self {
     UserId(uuid::Uuid::new_v4())
   }
+
+  /// Returns the nil user ID.
+  ///
+  /// # Returns
+  ///
+  /// * `Self` - The user ID with all bits set to zero.
+  pub const fn nil() -> Self {
+    UserId(uuid::Uuid::nil())
+  }
+
+  /// Creates a user ID from a 128-bit integer.
+  ///
+  /// This is useful for minting predictable IDs in tests.
+  ///
+  /// # Arguments
+  ///
+  /// * `value` - The integer to build the ID from.
+  ///
+  /// # Returns
+  ///
+  /// * `Self` - The user ID with the integer's bits.
+  pub const fn from_u128(value: u128) -> Self {
+    UserId(uuid::Uuid::from_u128(value))
+  }
+
+  /// Parses a user ID from a reference produced by `User::external_ref`.
+  ///
+  /// # Arguments
+  ///
+  /// * `value` - The base32 reference to parse.
+  ///
+  /// # Returns
+  ///
+  /// * `Result<Self, UserError>` - The user ID, or
+  ///   `UserError::InvalidExternalRef` if the reference is malformed.
+  pub fn from_external_ref(value: &str) -> Result<Self, UserError> {
+    base32::decode(value)
+      .and_then(|bytes| uuid::Uuid::from_slice(&bytes).ok())
+      .map(UserId)
+      .ok_or(UserError::InvalidExternalRef)
+  }
+
+  /// Returns the underlying UUID of the user ID.
+  ///
+  /// # Returns
+  ///
+  /// * `uuid::Uuid` - The wrapped UUID.
+  pub fn as_uuid(&self) -> uuid::Uuid {
+    self.0
+  }
+}
+
+impl From<uuid::Uuid> for UserId {
+  fn from(value: uuid::Uuid) -> Self {
+    UserId(value)
+  }
 }
 
 impl From<String> for UserId {
@@ -54,43 +113,81 @@ impl From<String> for UserId {
   }
 }
 
-#[derive(Debug, Clone, PartialEq, Eq)]
+// -- UserRole Structure -------------------------------------------
+/// Represents the assignment of a role to a user.
+#[derive(Debug, Clone)]
 pub struct UserRole {
   user_id: UserId,
   role_id: RoleId,
+  assigned_at: chrono::DateTime<chrono::Utc>,
 }
 
+// -- Implement PartialEq for UserRole, ignoring assigned_at.
+impl PartialEq for UserRole {
+  fn eq(&self, other: &Self) -> bool {
+    self.user_id == other.user_id && self.role_id == other.role_id
+  }
+}
+
+impl Eq for UserRole {}
+
 impl UserRole {
   /// Creates a new user role.
   ///
   /// # Arguments
   ///
-  /// * `user` - The user associated with the role.
-  /// * `role` - The role to assign to the user.
+  /// * `user_id` - The user associated with the role.
+  /// * `role_id` - The role to assign to the user.
   ///
   /// # Returns
   ///
   /// * `Self` - The new user role.
   pub fn new(user_id: UserId, role_id: RoleId) -> Self {
-    UserRole { user_id, role_id }
+    UserRole {
+      user_id,
+      role_id,
+      assigned_at: chrono::Utc::now(),
+    }
   }
 
+  /// Returns the ID of the user the role is assigned to.
+  ///
+  /// # Returns
+  ///
+  /// * `&UserId` - The ID of the user.
   pub fn user_id(&self) -> &UserId {
     &self.user_id
   }
 
+  /// Returns the ID of the assigned role.
+  ///
+  /// # Returns
+  ///
+  /// * `&RoleId` - The ID of the role.
   pub fn role_id(&self) -> &RoleId {
     &self.role_id
   }
+
+  /// Returns when the role was assigned to the user.
+  ///
+  /// # Returns
+  ///
+  /// * `&chrono::DateTime<chrono::Utc>` - The assignment date.
+  pub fn assigned_at(&self) -> &chrono::DateTime<chrono::Utc> {
+    &self.assigned_at
+  }
 }
 
 // -- UserName Structure -------------------------------------------
 /// Represents a user's name
-#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[derive(
+  Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize,
+)]
+#[serde(from = "String")]
 pub struct UserName(std::rc::Rc<str>);
 
 impl UserName {
-  /// Creates a new user name.
+  /// Creates a new user name, normalized to Unicode NFC form.
   ///
   /// # Arguments
   ///
@@ -100,7 +197,40 @@ impl UserName {
   ///
   /// * `Self` - The new user name.
   pub fn new(name: &str) -> Self {
-    UserName(std::rc::Rc::from(name))
+    let normalized: String = name.nfc().collect();
+    UserName(std::rc::Rc::from(normalized))
+  }
+
+  /// Returns the name as a string slice.
+  ///
+  /// # Returns
+  ///
+  /// * `&str` - The borrowed name, without allocating.
+  pub fn as_str(&self) -> &str {
+    &self.0
+  }
+
+  /// Returns the normalized form of the name, used for case-insensitive
+  /// comparisons.
+  ///
+  /// # Returns
+  ///
+  /// * `String` - The trimmed, lowercased name.
+  pub fn normalized(&self) -> String {
+    self.0.trim().to_lowercase()
+  }
+
+  /// Compares two names ignoring case and surrounding whitespace.
+  ///
+  /// # Arguments
+  ///
+  /// * `other` - The name to compare against.
+  ///
+  /// # Returns
+  ///
+  /// * `bool` - `true` if both names normalize to the same value.
+  pub fn eq_ignore_case(&self, other: &UserName) -> bool {
+    self.normalized() == other.normalized()
   }
 }
 
@@ -111,19 +241,79 @@ impl std::fmt::Display for UserName {
   }
 }
 
-#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+// -- Implement From<String> for UserName, so deserialized names are
+// normalized too.
+impl From<String> for UserName {
+  fn from(value: String) -> Self {
+    UserName::new(&value)
+  }
+}
+
+// -- Implement PartialEq<str> for UserName.
+impl PartialEq<str> for UserName {
+  fn eq(&self, other: &str) -> bool {
+    &*self.0 == other
+  }
+}
+
+// -- Implement PartialEq<&str> for UserName.
+impl PartialEq<&str> for UserName {
+  fn eq(&self, other: &&str) -> bool {
+    &*self.0 == *other
+  }
+}
+
+// -- UserError Enum -----------------------------------------------
+/// Represents an error raised while creating or modifying a user.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
 pub enum UserError {
+  /// The password and its confirmation did not match.
   PasswordMismatch,
+  /// The password was not valid.
   InvalidPassword,
+  /// Another user already has the name.
+  NameTaken,
+  /// Too many password verification attempts were made.
+  RateLimited,
+  /// The user has more roles than allowed.
+  TooManyRoles {
+    /// The maximum number of roles allowed.
+    max: usize,
+    /// The number of roles the user has.
+    actual: usize,
+  },
+  /// The user has a role that is not allowed.
+  RoleNotAllowed(RoleId),
+  /// The user does not have the role.
+  RoleNotFound(RoleId),
+  /// An external reference could not be parsed.
+  InvalidExternalRef,
+  /// An ID could not be parsed; carries the rejected value.
+  InvalidId(String),
+  /// A timestamp could not be parsed; carries the rejected value.
+  InvalidTimestamp(String),
+  /// The user name was empty.
+  EmptyName,
+  /// The stored password hash could not be parsed.
+  CorruptHash,
+  /// The role is assigned to the user more than once.
+  DuplicateRole(RoleId),
+  /// The role assignment belongs to a different user.
+  RoleUserMismatch(RoleId),
+  /// Two records that were expected to describe the same user have
+  /// different IDs.
+  IdMismatch,
+  /// Hashing the password failed.
+  HashingFailed(argon2::password_hash::Error),
 }
 
 impl std::error::Error for UserError {
   fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
-    None
-  }
-
-  fn cause(&self) -> Option<&dyn std::error::Error> {
-    self.source()
+    match self {
+      UserError::HashingFailed(err) => Some(err),
+      _ => None,
+    }
   }
 }
 
@@ -132,20 +322,113 @@ impl std::fmt::Display for UserError {
     match self {
       UserError::PasswordMismatch => write!(f, "Password mismatch"),
       UserError::InvalidPassword => write!(f, "Invalid password"),
+      UserError::NameTaken => write!(f, "User name is already taken"),
+      UserError::RateLimited => write!(f, "Too many verification attempts"),
+      UserError::TooManyRoles { max, actual } => {
+        write!(f, "User may have at most {max} roles (got {actual})")
+      }
+      UserError::RoleNotAllowed(role_id) => {
+        write!(f, "Role {role_id} is not allowed")
+      }
+      UserError::RoleNotFound(role_id) => {
+        write!(f, "Role {role_id} is not assigned to the user")
+      }
+      UserError::InvalidExternalRef => write!(f, "Invalid external reference"),
+      UserError::InvalidId(value) => write!(f, "Invalid ID: {value:?}"),
+      UserError::InvalidTimestamp(value) => {
+        write!(f, "Invalid timestamp: {value:?}")
+      }
+      UserError::EmptyName => write!(f, "User name cannot be empty"),
+      UserError::CorruptHash => write!(f, "Password hash is corrupt"),
+      UserError::DuplicateRole(role_id) => {
+        write!(f, "Role {role_id} is assigned more than once")
+      }
+      UserError::RoleUserMismatch(role_id) => {
+        write!(f, "Role {role_id} is assigned to a different user")
+      }
+      UserError::IdMismatch => write!(f, "User IDs do not match"),
+      UserError::HashingFailed(err) => {
+        write!(f, "Password hashing failed: {err}")
+      }
     }
   }
 }
 
 // -- User Structure -----------------------------------------------
 /// User represents a user in the system.
-#[derive(Debug, Clone, PartialEq, Eq)]
+///
+/// Equality compares the user's data only. The attempts recorded by
+/// `verify_rate_limited` are runtime state and are ignored.
+#[derive(Debug, Clone)]
 pub struct User {
   id: UserId,
   name: UserName,
   password: UserPassword,
-  created_at: chrono::DateTime<chrono::Local>,
-  updated_at: Option<chrono::DateTime<chrono::Local>>,
+  created_at: chrono::DateTime<chrono::Utc>,
+  updated_at: Option<chrono::DateTime<chrono::Utc>>,
+  password_changed_at: chrono::DateTime<chrono::Utc>,
   roles: Vec<UserRole>,
+  verify_attempts: Vec<chrono::DateTime<chrono::Utc>>,
+}
+
+// -- Implement PartialEq for User, ignoring verify_attempts.
+impl PartialEq for User {
+  fn eq(&self, other: &Self) -> bool {
+    self.id == other.id
+      && self.name == other.name
+      && self.password == other.password
+      && self.created_at == other.created_at
+      && self.updated_at == other.updated_at
+      && self.password_changed_at == other.password_changed_at
+      && self.roles == other.roles
+  }
+}
+
+impl Eq for User {}
+
+// -- Implement Display for User.
+impl std::fmt::Display for User {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    let count = self.roles.len();
+    let noun = if count == 1 { "role" } else { "roles" };
+    write!(f, "{} ({}) [{count} {noun}]", self.name, self.id)
+  }
+}
+
+// -- Implement IntoIterator for &User over its role assignments.
+impl<'a> IntoIterator for &'a User {
+  type Item = &'a UserRole;
+  type IntoIter = std::slice::Iter<'a, UserRole>;
+
+  fn into_iter(self) -> Self::IntoIter {
+    self.roles.iter()
+  }
+}
+
+// -- Implement ordering for User by creation date, then ID. The remaining
+// compared fields only break ties, keeping the order consistent with
+// PartialEq.
+impl PartialOrd for User {
+  fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+    Some(self.cmp(other))
+  }
+}
+
+impl Ord for User {
+  fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+    self
+      .created_at
+      .cmp(&other.created_at)
+      .then_with(|| self.id.cmp(&other.id))
+      .then_with(|| self.name.cmp(&other.name))
+      .then_with(|| self.password.0.cmp(&other.password.0))
+      .then_with(|| self.updated_at.cmp(&other.updated_at))
+      .then_with(|| self.password_changed_at.cmp(&other.password_changed_at))
+      .then_with(|| {
+        let key = |role: &UserRole| (role.user_id, role.role_id);
+        self.roles.iter().map(key).cmp(other.roles.iter().map(key))
+      })
+  }
 }
 
 impl User {
@@ -154,6 +437,7 @@ impl User {
   /// # Arguments
   ///
   /// * `name` - The name of the user.
+  /// * `password` - The password of the user.
   ///
   /// # Returns
   ///
@@ -162,23 +446,117 @@ impl User {
     name: UserName,
     password: UserPassword,
   ) -> Result<Self, Box<dyn std::error::Error>> {
+    let now = chrono::Utc::now();
     let user = User {
       id: UserId::new(),
       name,
       password,
-      created_at: chrono::Local::now(),
+      created_at: now,
       updated_at: None,
+      password_changed_at: now,
       roles: Vec::new(),
+      verify_attempts: Vec::new(),
     };
     Ok(user)
   }
 
+  /// Returns the password of the user.
+  ///
+  /// # Returns
+  ///
+  /// * `&UserPassword` - The hashed password of the user.
   pub fn password(&self) -> &UserPassword {
     &self.password
   }
 
+  /// Replaces the user's password.
+  ///
+  /// # Arguments
+  ///
+  /// * `password` - The new password.
+  ///
+  /// # Returns
+  ///
+  /// * `&mut Self` - A mutable reference to the user.
+  pub fn set_password(&mut self, password: UserPassword) -> &mut Self {
+    self.password = password;
+    self.password_changed_at = chrono::Utc::now();
+    self.touch()
+  }
+
+  /// Returns when the user's password was last changed.
+  ///
+  /// # Returns
+  ///
+  /// * `&chrono::DateTime<chrono::Utc>` - The password change date.
+  pub fn password_changed_at(&self) -> &chrono::DateTime<chrono::Utc> {
+    &self.password_changed_at
+  }
+
+  /// Returns how long ago the user's password was last changed.
+  ///
+  /// # Returns
+  ///
+  /// * `chrono::Duration` - The age of the password.
+  pub fn password_age(&self) -> chrono::Duration {
+    chrono::Utc::now() - self.password_changed_at
+  }
+
+  /// Returns whether the user's password is older than the allowed age.
+  ///
+  /// # Arguments
+  ///
+  /// * `max_age` - The maximum allowed password age.
+  ///
+  /// # Returns
+  ///
+  /// * `bool` - `true` if the password must be rotated.
+  pub fn is_password_expired(&self, max_age: chrono::Duration) -> bool {
+    self.password_age() > max_age
+  }
+
+  /// Verifies a plaintext password against the user's password.
+  ///
+  /// # Arguments
+  ///
+  /// * `password` - The password to verify.
+  ///
+  /// # Returns
+  ///
+  /// * `bool` - `true` if the password matches.
   pub fn verify_password(&self, password: &str) -> bool {
-    self.password.verify(password).is_ok()
+    self.password.verify(password).unwrap_or(false)
+  }
+
+  /// Verifies the password, refusing once too many attempts have been made
+  /// within a sliding time window.
+  ///
+  /// Every accepted attempt is recorded, whether or not the password matches.
+  ///
+  /// # Arguments
+  ///
+  /// * `password` - The password to verify.
+  /// * `max_per_window` - The number of attempts allowed within `window`.
+  /// * `window` - The length of the sliding window.
+  ///
+  /// # Returns
+  ///
+  /// * `Result<bool, UserError>` - Whether the password matches, or
+  ///   `UserError::RateLimited` if the attempt limit has been reached.
+  pub fn verify_rate_limited(
+    &mut self,
+    password: &str,
+    max_per_window: u32,
+    window: chrono::Duration,
+  ) -> Result<bool, UserError> {
+    let now = chrono::Utc::now();
+    let cutoff = now - window;
+    self.verify_attempts.retain(|attempt| *attempt > cutoff);
+    if self.verify_attempts.len() >= max_per_window as usize {
+      return Err(UserError::RateLimited);
+    }
+    self.verify_attempts.push(now);
+    Ok(self.verify_password(password))
   }
 
   /// Adds a role to the user.
@@ -191,10 +569,7 @@ impl User {
   ///
   /// * `Self` - The updated user.
   pub fn with_role(mut self, role_id: RoleId) -> Self {
-    self.roles.push(UserRole {
-      user_id: self.id,
-      role_id,
-    });
+    self.roles.push(UserRole::new(self.id, role_id));
     self
   }
 
@@ -208,13 +583,61 @@ impl User {
   ///
   /// * `&mut Self` - A mutable reference to the user.
   pub fn add_role(&mut self, role_id: RoleId) -> &mut Self {
-    self.roles.push(UserRole {
-      user_id: self.id,
-      role_id,
-    });
+    self.roles.push(UserRole::new(self.id, role_id));
+    self.touch()
+  }
+
+  /// Adds several roles to the user, skipping any it already has.
+  ///
+  /// # Arguments
+  ///
+  /// * `role_ids` - The roles to add to the user.
+  ///
+  /// # Returns
+  ///
+  /// * `Self` - The updated user.
+  pub fn with_roles(
+    mut self,
+    role_ids: impl IntoIterator<Item = RoleId>,
+  ) -> Self {
+    self.push_unique_roles(role_ids);
+    self
+  }
+
+  /// Adds several roles to the user, skipping any it already has.
+  ///
+  /// # Arguments
+  ///
+  /// * `role_ids` - The roles to add to the user.
+  ///
+  /// # Returns
+  ///
+  /// * `&mut Self` - A mutable reference to the user.
+  pub fn add_roles(
+    &mut self,
+    role_ids: impl IntoIterator<Item = RoleId>,
+  ) -> &mut Self {
+    if self.push_unique_roles(role_ids) {
+      self.touch();
+    }
     self
   }
 
+  // Pushes each role the user does not already have, returning whether any
+  // role was added.
+  fn push_unique_roles(
+    &mut self,
+    role_ids: impl IntoIterator<Item = RoleId>,
+  ) -> bool {
+    let before = self.roles.len();
+    for role_id in role_ids {
+      if !self.roles.iter().any(|r| r.role_id == role_id) {
+        self.roles.push(UserRole::new(self.id, role_id));
+      }
+    }
+    self.roles.len() > before
+  }
+
   /// Removes a role from the user.
   ///
   /// # Arguments
@@ -225,7 +648,84 @@ impl User {
   ///
   /// * `&mut Self` - A mutable reference to the user.
   pub fn remove_role(&mut self, role_id: RoleId) -> &mut Self {
+    self.try_remove_role(role_id);
+    self
+  }
+
+  /// Removes a role from the user, reporting whether it was assigned.
+  ///
+  /// # Arguments
+  ///
+  /// * `role_id` - The role to remove from the user.
+  ///
+  /// # Returns
+  ///
+  /// * `bool` - `true` if the role was assigned and has been removed.
+  pub fn try_remove_role(&mut self, role_id: RoleId) -> bool {
+    let before = self.roles.len();
     self.roles.retain(|r| r.role_id != role_id);
+    let removed = self.roles.len() != before;
+    if removed {
+      self.touch();
+    }
+    removed
+  }
+
+  /// Removes a role from the user, failing if it is not assigned.
+  ///
+  /// # Arguments
+  ///
+  /// * `role_id` - The role to remove from the user.
+  ///
+  /// # Returns
+  ///
+  /// * `Result<(), UserError>` - `UserError::RoleNotFound` if the user does
+  ///   not have the role.
+  pub fn require_and_remove_role(
+    &mut self,
+    role_id: RoleId,
+  ) -> Result<(), UserError> {
+    if self.try_remove_role(role_id) {
+      Ok(())
+    } else {
+      Err(UserError::RoleNotFound(role_id))
+    }
+  }
+
+  /// Removes every role from the user.
+  ///
+  /// # Returns
+  ///
+  /// * `&mut Self` - A mutable reference to the user.
+  pub fn clear_roles(&mut self) -> &mut Self {
+    if !self.roles.is_empty() {
+      self.roles.clear();
+      self.touch();
+    }
+    self
+  }
+
+  /// Renames the user.
+  ///
+  /// # Arguments
+  ///
+  /// * `new_name` - The new name of the user.
+  ///
+  /// # Returns
+  ///
+  /// * `&mut Self` - A mutable reference to the user.
+  pub fn rename(&mut self, new_name: UserName) -> &mut Self {
+    self.name = new_name;
+    self.touch()
+  }
+
+  /// Marks the user as updated now.
+  ///
+  /// # Returns
+  ///
+  /// * `&mut Self` - A mutable reference to the user.
+  pub fn touch(&mut self) -> &mut Self {
+    self.updated_at = Some(chrono::Utc::now());
     self
   }
 
@@ -251,8 +751,8 @@ impl User {
   ///
   /// # Returns
   ///
-  /// * `&chrono::DateTime<chrono::Local>` - The creation date of the user.
-  pub fn created_at(&self) -> &chrono::DateTime<chrono::Local> {
+  /// * `&chrono::DateTime<chrono::Utc>` - The creation date of the user.
+  pub fn created_at(&self) -> &chrono::DateTime<chrono::Utc> {
     &self.created_at
   }
 
@@ -260,8 +760,8 @@ impl User {
   ///
   /// # Returns
   ///
-  /// * `Option<&chrono::DateTime<chrono::Local>>` - The update date of the user.
-  pub fn updated_at(&self) -> Option<&chrono::DateTime<chrono::Local>> {
+  /// * `Option<&chrono::DateTime<chrono::Utc>>` - The update date of the user.
+  pub fn updated_at(&self) -> Option<&chrono::DateTime<chrono::Utc>> {
     self.updated_at.as_ref()
   }
 
@@ -269,68 +769,744 @@ impl User {
   ///
   /// # Returns
   ///
-  /// * `&Vec<Rc<UserRole>>` - The roles of the user.
-  pub fn roles(&self) -> &Vec<UserRole> {
+  /// * `&[UserRole]` - The roles of the user.
+  pub fn roles(&self) -> &[UserRole] {
     &self.roles
   }
-}
-
-// -- UserPassword Structure ---------------------------------------
-#[derive(Debug, Clone, PartialEq, Eq)]
-pub struct UserPassword(String);
 
-impl UserPassword {
-  /// Creates a new user password.
+  /// Returns the user's assignment of the given role.
   ///
   /// # Arguments
   ///
-  /// * `password` - The password of the user.
+  /// * `role_id` - The role to look up.
   ///
   /// # Returns
   ///
-  /// * `Self` - The new user password.
-  pub fn new(
-    password: &str,
-    confirm_password: &str,
-  ) -> Result<Self, UserError> {
-    if password != confirm_password {
-      return Err(UserError::PasswordMismatch);
-    }
-    let salt = SaltString::generate(&mut OsRng);
+  /// * `Option<&UserRole>` - The assignment, or `None` if the role is not
+  ///   assigned.
+  pub fn user_role(&self, role_id: &RoleId) -> Option<&UserRole> {
+    self.roles.iter().find(|r| r.role_id == *role_id)
+  }
 
-    // Argon2 with default params (Argon2id v19)
-    let argon2 = Argon2::default();
+  /// Returns the number of roles assigned to the user.
+  ///
+  /// # Returns
+  ///
+  /// * `usize` - The number of roles.
+  pub fn role_count(&self) -> usize {
+    self.roles.len()
+  }
 
-    // Hash password to PHC string ($argon2id$v=19$...)
-    let password_hash = match argon2.hash_password(password.as_bytes(), &salt) {
-      Ok(hash) => Self(hash.to_string()),
-      Err(_err) => exit(1),
-    };
-    Ok(password_hash)
+  /// Returns whether the user has any role at all.
+  ///
+  /// # Returns
+  ///
+  /// * `bool` - `true` if at least one role is assigned.
+  pub fn has_any_role(&self) -> bool {
+    !self.roles.is_empty()
   }
 
+  /// Returns an iterator over the role IDs of the user.
+  ///
+  /// # Returns
+  ///
+  /// * `impl Iterator<Item = &RoleId>` - The role IDs assigned to the user.
+  pub fn roles_iter(&self) -> impl Iterator<Item = &RoleId> {
+    self.roles.iter().map(UserRole::role_id)
+  }
+
+  /// Merges another version of the same user into this one.
+  ///
+  /// The name comes from whichever version was updated most recently, the
+  /// password from whichever was changed most recently, and the roles of
+  /// both are combined.
+  ///
+  /// # Arguments
+  ///
+  /// * `other` - The other version of the user.
+  ///
+  /// # Returns
+  ///
+  /// * `Result<(), UserError>` - `UserError::IdMismatch` if `other` is a
+  ///   different user, in which case nothing is changed.
+  pub fn merge(&mut self, other: &User) -> Result<(), UserError> {
+    if self.id != other.id {
+      return Err(UserError::IdMismatch);
+    }
+    if other.updated_at > self.updated_at {
+      self.name = other.name.clone();
+      self.updated_at = other.updated_at;
+    }
+    if other.password_changed_at > self.password_changed_at {
+      self.password = other.password.clone();
+      self.password_changed_at = other.password_changed_at;
+    }
+    self.created_at = self.created_at.min(other.created_at);
+    for role in &other.roles {
+      if !self.roles.contains(role) {
+        self.roles.push(role.clone());
+      }
+    }
+    self
+      .verify_attempts
+      .extend_from_slice(&other.verify_attempts);
+    self.verify_attempts.sort();
+    Ok(())
+  }
+
+  /// Checks every invariant of the user.
+  ///
+  /// # Returns
+  ///
+  /// * `Result<(), Vec<UserError>>` - `Ok` if the user is valid, otherwise
+  ///   every violation found.
+  pub fn validate(&self) -> Result<(), Vec<UserError>> {
+    let mut errors = Vec::new();
+    if self.name.as_str().trim().is_empty() {
+      errors.push(UserError::EmptyName);
+    }
+    if PasswordHash::new(&self.password.0).is_err() {
+      errors.push(UserError::CorruptHash);
+    }
+    let mut seen = HashSet::new();
+    for role in &self.roles {
+      if !seen.insert(role.role_id) {
+        errors.push(UserError::DuplicateRole(role.role_id));
+      }
+      if role.user_id != self.id {
+        errors.push(UserError::RoleUserMismatch(role.role_id));
+      }
+    }
+    if errors.is_empty() {
+      Ok(())
+    } else {
+      Err(errors)
+    }
+  }
+
+  /// Captures the full state of the user.
+  ///
+  /// The attempts recorded by `verify_rate_limited` are not part of the
+  /// snapshot.
+  ///
+  /// # Returns
+  ///
+  /// * `UserSnapshot` - A snapshot that can later be passed to
+  ///   `restore_from`.
+  pub fn snapshot(&self) -> UserSnapshot {
+    UserSnapshot::from(self)
+  }
+
+  /// Restores the user to a previously captured state.
+  ///
+  /// The attempts recorded by `verify_rate_limited` are kept, so restoring
+  /// does not lift a rate limit.
+  ///
+  /// # Arguments
+  ///
+  /// * `snapshot` - The snapshot to restore.
+  ///
+  /// # Returns
+  ///
+  /// * `Result<(), UserError>` - An error if the snapshot cannot be parsed,
+  ///   in which case the user is left unchanged.
+  pub fn restore_from(
+    &mut self,
+    snapshot: UserSnapshot,
+  ) -> Result<(), UserError> {
+    let restored = User::try_from(snapshot)?;
+    let verify_attempts = std::mem::take(&mut self.verify_attempts);
+    *self = User {
+      verify_attempts,
+      ..restored
+    };
+    Ok(())
+  }
+
+  /// Returns a short, URL-safe reference derived from the user's ID.
+  ///
+  /// # Returns
+  ///
+  /// * `String` - The user ID's bytes encoded as lowercase base32.
+  pub fn external_ref(&self) -> String {
+    base32::encode(self.id.0.as_bytes())
+  }
+
+  /// Returns a view of the user that is safe to expose publicly.
+  ///
+  /// # Returns
+  ///
+  /// * `UserPublic` - The user without its password hash.
+  pub fn to_public(&self) -> UserPublic {
+    UserPublic {
+      id: self.id,
+      name: self.name.clone(),
+      roles: self.roles.iter().map(|r| r.role_id).collect(),
+      created_at: self.created_at,
+      updated_at: self.updated_at,
+    }
+  }
+
+  /// Looks up the full roles assigned to the user.
+  ///
+  /// # Arguments
+  ///
+  /// * `repo` - The repository to look the roles up in.
+  ///
+  /// # Returns
+  ///
+  /// * `Vec<Role>` - The roles found in the repository. Role IDs missing from
+  ///   the repository are skipped.
+  pub fn resolve_roles<R: RoleRepository>(&self, repo: &R) -> Vec<Role> {
+    self.roles_iter().filter_map(|id| repo.get(id)).collect()
+  }
+
+  /// Returns the roles of the user as a deduplicated set.
+  ///
+  /// # Returns
+  ///
+  /// * `RoleSet` - The role IDs assigned to the user.
+  pub fn role_set(&self) -> RoleSet {
+    self.roles.iter().map(|r| r.role_id).collect()
+  }
+}
+
+// -- UserSnapshot Structure ---------------------------------------
+/// A flat, serde-friendly copy of a user's state.
+///
+/// IDs are stored as strings and timestamps as RFC 3339 strings. The password
+/// cannot be recovered from its hash, so the PHC hash string is carried
+/// verbatim. Rate-limit attempts are not stored, and a user built from a
+/// snapshot starts with none.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct UserSnapshot {
+  /// The user's ID.
+  pub id: String,
+  /// The user's name.
+  pub name: String,
+  /// The user's password as an Argon2 PHC string.
+  pub password_hash: String,
+  /// The user's role assignments.
+  pub roles: Vec<UserRoleSnapshot>,
+  /// When the user was created.
+  pub created_at: String,
+  /// When the user was last updated, if ever.
+  pub updated_at: Option<String>,
+  /// When the user's password was last changed.
+  pub password_changed_at: String,
+}
+
+impl From<&User> for UserSnapshot {
+  fn from(user: &User) -> Self {
+    let timestamp = |value: &chrono::DateTime<chrono::Utc>| {
+      value.to_rfc3339_opts(chrono::SecondsFormat::AutoSi, true)
+    };
+    UserSnapshot {
+      id: user.id.to_string(),
+      name: user.name.to_string(),
+      password_hash: user.password.0.clone(),
+      roles: user
+        .roles
+        .iter()
+        .map(|role| UserRoleSnapshot {
+          role_id: role.role_id.to_string(),
+          assigned_at: timestamp(&role.assigned_at),
+        })
+        .collect(),
+      created_at: timestamp(&user.created_at),
+      updated_at: user.updated_at.as_ref().map(timestamp),
+      password_changed_at: timestamp(&user.password_changed_at),
+    }
+  }
+}
+
+impl TryFrom<UserSnapshot> for User {
+  type Error = UserError;
+
+  fn try_from(snapshot: UserSnapshot) -> Result<Self, Self::Error> {
+    let parse_uuid = |value: &str| {
+      uuid::Uuid::parse_str(value)
+        .map_err(|_| UserError::InvalidId(value.to_string()))
+    };
+    let parse_timestamp = |value: &str| {
+      chrono::DateTime::parse_from_rfc3339(value)
+        .map(|parsed| parsed.with_timezone(&chrono::Utc))
+        .map_err(|_| UserError::InvalidTimestamp(value.to_string()))
+    };
+
+    let id = UserId(parse_uuid(&snapshot.id)?);
+    let roles = snapshot
+      .roles
+      .iter()
+      .map(|role| {
+        Ok(UserRole {
+          user_id: id,
+          role_id: RoleId::from(parse_uuid(&role.role_id)?),
+          assigned_at: parse_timestamp(&role.assigned_at)?,
+        })
+      })
+      .collect::<Result<Vec<_>, UserError>>()?;
+    Ok(User {
+      id,
+      name: UserName::new(&snapshot.name),
+      password: UserPassword::from_phc(&snapshot.password_hash)?,
+      created_at: parse_timestamp(&snapshot.created_at)?,
+      updated_at: snapshot
+        .updated_at
+        .as_deref()
+        .map(parse_timestamp)
+        .transpose()?,
+      password_changed_at: parse_timestamp(&snapshot.password_changed_at)?,
+      roles,
+      verify_attempts: Vec::new(),
+    })
+  }
+}
+
+// -- UserRoleSnapshot Structure -----------------------------------
+/// A flat, serde-friendly copy of a role assignment, stored in a
+/// `UserSnapshot`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct UserRoleSnapshot {
+  /// The ID of the assigned role.
+  pub role_id: String,
+  /// When the role was assigned, as an RFC 3339 string.
+  pub assigned_at: String,
+}
+
+// -- UserPublic Structure -----------------------------------------
+/// A public view of a user, such as for API responses.
+///
+/// The password hash is never part of this structure, so it cannot leak
+/// through serialization.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct UserPublic {
+  /// The user's ID.
+  pub id: UserId,
+  /// The user's name.
+  pub name: UserName,
+  /// The IDs of the user's roles.
+  pub roles: Vec<RoleId>,
+  /// When the user was created.
+  pub created_at: chrono::DateTime<chrono::Utc>,
+  /// When the user was last updated, if ever.
+  pub updated_at: Option<chrono::DateTime<chrono::Utc>>,
+}
+
+// -- CreationConstraints Structure --------------------------------
+/// Constraints a new user must satisfy before it is created.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct CreationConstraints {
+  max_roles: Option<usize>,
+  allowed_roles: Option<HashSet<RoleId>>,
+}
+
+impl CreationConstraints {
+  /// Creates constraints that only require a unique user name.
+  ///
+  /// # Returns
+  ///
+  /// * `Self` - The new constraints.
+  pub fn new() -> Self {
+    Self::default()
+  }
+
+  /// Limits the number of roles a new user may have.
+  ///
+  /// # Arguments
+  ///
+  /// * `max` - The maximum number of roles.
+  ///
+  /// # Returns
+  ///
+  /// * `Self` - The updated constraints.
+  pub fn with_max_roles(mut self, max: usize) -> Self {
+    self.max_roles = Some(max);
+    self
+  }
+
+  /// Restricts the roles a new user may have.
+  ///
+  /// # Arguments
+  ///
+  /// * `role_ids` - The roles a new user may be assigned.
+  ///
+  /// # Returns
+  ///
+  /// * `Self` - The updated constraints.
+  pub fn with_allowed_roles(
+    mut self,
+    role_ids: impl IntoIterator<Item = RoleId>,
+  ) -> Self {
+    self.allowed_roles = Some(role_ids.into_iter().collect());
+    self
+  }
+}
+
+// -- User Collection Helpers --------------------------------------
+/// Ranks roles by the number of users holding them.
+///
+/// # Arguments
+///
+/// * `roles` - The roles to rank.
+/// * `users` - The users whose roles are counted.
+///
+/// # Returns
+///
+/// * `Vec<(RoleId, usize)>` - Each role ID with its holder count, sorted from
+///   most to least held. Roles with equal counts keep their input order.
+pub fn roles_by_usage(roles: &[Role], users: &[User]) -> Vec<(RoleId, usize)> {
+  let role_sets: Vec<RoleSet> = users.iter().map(User::role_set).collect();
+  let mut usage: Vec<(RoleId, usize)> = roles
+    .iter()
+    .map(|role| {
+      let id = role.id();
+      (id, role_sets.iter().filter(|set| set.contains(&id)).count())
+    })
+    .collect();
+  usage.sort_by_key(|&(_, count)| std::cmp::Reverse(count));
+  usage
+}
+
+/// Checks whether a user gained a privileged role between two snapshots.
+///
+/// # Arguments
+///
+/// * `before` - The user before the change.
+/// * `after` - The user after the change.
+/// * `privileged` - The role IDs considered privileged.
+///
+/// # Returns
+///
+/// * `bool` - `true` if `after` holds a privileged role that `before` did not.
+pub fn gained_privileged_role(
+  before: &User,
+  after: &User,
+  privileged: &HashSet<RoleId>,
+) -> bool {
+  let previous = before.role_set();
+  after
+    .role_set()
+    .difference(&previous)
+    .iter()
+    .any(|id| privileged.contains(id))
+}
+
+/// Returns a single page of users.
+///
+/// # Arguments
+///
+/// * `users` - The users to paginate.
+/// * `page` - The zero-based page index.
+/// * `per_page` - The number of users per page.
+///
+/// # Returns
+///
+/// * `&[User]` - The users on the requested page, or an empty slice if the
+///   page is out of range.
+pub fn paginate_users(users: &[User], page: usize, per_page: usize) -> &[User] {
+  let start = page.saturating_mul(per_page).min(users.len());
+  let end = start.saturating_add(per_page).min(users.len());
+  &users[start..end]
+}
+
+/// Finds the oldest and newest users by creation date.
+///
+/// # Arguments
+///
+/// * `users` - The users to search.
+///
+/// # Returns
+///
+/// * `Option<(&User, &User)>` - The oldest and newest users, or `None` if
+///   `users` is empty.
+pub fn account_age_extremes(users: &[User]) -> Option<(&User, &User)> {
+  let oldest = users.iter().min_by_key(|u| u.created_at)?;
+  let newest = users.iter().max_by_key(|u| u.created_at)?;
+  Some((oldest, newest))
+}
+
+/// Checks whether a candidate user can be created.
+///
+/// # Arguments
+///
+/// * `candidate` - The user to be created.
+/// * `existing` - The users that already exist.
+/// * `constraints` - The constraints the candidate must satisfy.
+///
+/// # Returns
+///
+/// * `Result<(), Vec<UserError>>` - `Ok` if the candidate satisfies every
+///   constraint, otherwise every violation found.
+pub fn can_create_user(
+  candidate: &User,
+  existing: &[User],
+  constraints: &CreationConstraints,
+) -> Result<(), Vec<UserError>> {
+  let mut errors = Vec::new();
+  if existing
+    .iter()
+    .any(|u| u.name.eq_ignore_case(&candidate.name))
+  {
+    errors.push(UserError::NameTaken);
+  }
+  let role_set = candidate.role_set();
+  if let Some(max) = constraints.max_roles
+    && role_set.len() > max
+  {
+    errors.push(UserError::TooManyRoles {
+      max,
+      actual: role_set.len(),
+    });
+  }
+  if let Some(allowed) = &constraints.allowed_roles {
+    errors.extend(
+      role_set
+        .iter()
+        .filter(|id| !allowed.contains(id))
+        .map(|id| UserError::RoleNotAllowed(*id)),
+    );
+  }
+  if errors.is_empty() {
+    Ok(())
+  } else {
+    Err(errors)
+  }
+}
+
+// -- UserPassword Structure ---------------------------------------
+/// Represents a user's password, stored as an Argon2 PHC hash string.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UserPassword(String);
+
+impl UserPassword {
+  /// Creates a new user password.
+  ///
+  /// # Arguments
+  ///
+  /// * `password` - The password of the user.
+  /// * `confirm_password` - The confirmation of the password.
+  ///
+  /// # Returns
+  ///
+  /// * `Self` - The new user password.
+  pub fn new(
+    password: impl AsRef<str>,
+    confirm_password: impl AsRef<str>,
+  ) -> Result<Self, UserError> {
+    // Argon2 with default params (Argon2id v19)
+    Self::hash_with(
+      &Argon2::default(),
+      password.as_ref(),
+      confirm_password.as_ref(),
+    )
+  }
+
+  /// Rehydrates a password from a stored Argon2 PHC string without
+  /// re-hashing.
+  ///
+  /// # Arguments
+  ///
+  /// * `hash` - The PHC string, such as `$argon2id$v=19$...`.
+  ///
+  /// # Returns
+  ///
+  /// * `Result<Self, UserError>` - The password, or `UserError::CorruptHash`
+  ///   if the string is not a valid PHC hash.
+  pub fn from_phc(hash: &str) -> Result<Self, UserError> {
+    PasswordHash::new(hash).map_err(|_| UserError::CorruptHash)?;
+    Ok(Self(hash.to_string()))
+  }
+
+  /// Creates a new user password peppered with an application secret.
+  ///
+  /// The secret is not stored in the hash, so the same secret must be passed
+  /// to `verify_with_secret`.
+  ///
+  /// # Arguments
+  ///
+  /// * `password` - The password of the user.
+  /// * `confirm_password` - The confirmation of the password.
+  /// * `secret` - The application-wide secret key.
+  ///
+  /// # Returns
+  ///
+  /// * `Self` - The new user password.
+  pub fn new_with_secret(
+    password: impl AsRef<str>,
+    confirm_password: impl AsRef<str>,
+    secret: &[u8],
+  ) -> Result<Self, UserError> {
+    let argon2 = Self::argon2_with_secret(secret)?;
+    Self::hash_with(&argon2, password.as_ref(), confirm_password.as_ref())
+  }
+
+  /// Verifies a plaintext password against the hash.
+  ///
+  /// # Arguments
+  ///
+  /// * `password` - The password to verify.
+  ///
+  /// # Returns
+  ///
+  /// * `Result<bool, Box<dyn std::error::Error>>` - Whether the password
+  ///   matches.
   pub fn verify(
     &self,
-    password: &str,
+    password: impl AsRef<str>,
   ) -> Result<bool, Box<dyn std::error::Error>> {
-    let argon2 = Argon2::default();
-    match PasswordHash::new(&self.0) {
-      Ok(parsed_hash) => {
-        let result = argon2
-          .verify_password(password.as_bytes(), &parsed_hash)
-          .is_ok();
-        Ok(result)
-      }
+    Ok(self.verify_with(&Argon2::default(), password.as_ref()))
+  }
+
+  /// Verifies a password hashed with `new_with_secret`.
+  ///
+  /// # Arguments
+  ///
+  /// * `password` - The password to verify.
+  /// * `secret` - The application-wide secret key.
+  ///
+  /// # Returns
+  ///
+  /// * `Result<bool, Box<dyn std::error::Error>>` - Whether the password
+  ///   matches.
+  pub fn verify_with_secret(
+    &self,
+    password: impl AsRef<str>,
+    secret: &[u8],
+  ) -> Result<bool, Box<dyn std::error::Error>> {
+    let argon2 = Self::argon2_with_secret(secret)?;
+    Ok(self.verify_with(&argon2, password.as_ref()))
+  }
+
+  /// Checks whether the hash was made with parameters at least as costly as
+  /// a floor.
+  ///
+  /// # Arguments
+  ///
+  /// * `floor` - The minimum acceptable Argon2 parameters.
+  ///
+  /// # Returns
+  ///
+  /// * `Result<bool, UserError>` - `true` if the memory, iteration and
+  ///   parallelism costs all meet the floor, or `UserError::CorruptHash` if
+  ///   the parameters cannot be read from the hash.
+  pub fn meets_minimum(
+    &self,
+    floor: &argon2::Params,
+  ) -> Result<bool, UserError> {
+    let hash =
+      PasswordHash::new(&self.0).map_err(|_| UserError::CorruptHash)?;
+    let params =
+      argon2::Params::try_from(&hash).map_err(|_| UserError::CorruptHash)?;
+    Ok(
+      params.m_cost() >= floor.m_cost()
+        && params.t_cost() >= floor.t_cost()
+        && params.p_cost() >= floor.p_cost(),
+    )
+  }
+
+  /// Verifies a password and, if it matches but the hash is weaker than the
+  /// policy, rehashes it with the policy's parameters.
+  ///
+  /// # Arguments
+  ///
+  /// * `password` - The password to verify.
+  /// * `policy` - The Argon2 parameters the hash should meet.
+  ///
+  /// # Returns
+  ///
+  /// * `Result<Option<UserPassword>, UserError>` - The upgraded password, or
+  ///   `None` if the password does not match or the hash already meets the
+  ///   policy.
+  pub fn verify_and_upgrade(
+    &self,
+    password: impl AsRef<str>,
+    policy: &argon2::Params,
+  ) -> Result<Option<UserPassword>, UserError> {
+    let password = password.as_ref();
+    if !self.verify_with(&Argon2::default(), password)
+      || self.meets_minimum(policy)?
+    {
+      return Ok(None);
+    }
+    let argon2 = Argon2::new(
+      argon2::Algorithm::default(),
+      argon2::Version::default(),
+      policy.clone(),
+    );
+    Self::hash_with(&argon2, password, password).map(Some)
+  }
+
+  fn argon2_with_secret(secret: &[u8]) -> Result<Argon2<'_>, UserError> {
+    Argon2::new_with_secret(
+      secret,
+      argon2::Algorithm::default(),
+      argon2::Version::default(),
+      argon2::Params::default(),
+    )
+    .map_err(|err| UserError::HashingFailed(err.into()))
+  }
+
+  fn hash_with(
+    argon2: &Argon2,
+    password: &str,
+    confirm_password: &str,
+  ) -> Result<Self, UserError> {
+    if !bool::from(password.as_bytes().ct_eq(confirm_password.as_bytes())) {
+      return Err(UserError::PasswordMismatch);
+    }
+    let salt = SaltString::generate(&mut OsRng);
+
+    // Hash password to PHC string ($argon2id$v=19$...)
+    let password_hash = argon2
+      .hash_password(password.as_bytes(), &salt)
+      .map_err(UserError::HashingFailed)?;
+    Ok(Self(password_hash.to_string()))
+  }
 
-      Err(_) => Ok(false),
+  fn verify_with(&self, argon2: &Argon2, password: &str) -> bool {
+    match PasswordHash::new(&self.0) {
+      Ok(parsed_hash) => argon2
+        .verify_password(password.as_bytes(), &parsed_hash)
+        .is_ok(),
+      Err(_) => false,
     }
   }
 }
 
+// -- Argon2 Cost Helpers ------------------------------------------
+/// Compares the combined cost of two sets of Argon2 parameters.
+///
+/// The combined cost is the product of the memory, iteration and parallelism
+/// costs, so doubling any one of them doubles the score.
+///
+/// # Arguments
+///
+/// * `params` - The parameters to score.
+/// * `reference` - The parameters of the reference machine.
+///
+/// # Returns
+///
+/// * `f64` - The cost of `params` as a multiple of the cost of `reference`.
+pub fn relative_cost(
+  params: &argon2::Params,
+  reference: &argon2::Params,
+) -> f64 {
+  let combined = |params: &argon2::Params| {
+    f64::from(params.m_cost())
+      * f64::from(params.t_cost())
+      * f64::from(params.p_cost())
+  };
+  combined(params) / combined(reference)
+}
+
 // -- Tests ------------------------------------------------------------------
 #[cfg(test)]
 mod tests {
-  use crate::role::{Role, RoleName};
+  use crate::{
+    InMemoryRoleRepository,
+    role::{Role, RoleName},
+  };
 
   use super::*;
 
@@ -360,6 +1536,28 @@ mod tests {
     Ok(())
   }
 
+  #[test]
+  fn add_roles_in_bulk() -> Result<(), Box<dyn std::error::Error>> {
+    let admin = RoleId::new();
+    let editor = RoleId::new();
+    let user = User::new(
+      UserName::new("bob"),
+      UserPassword::new("password", "password")?,
+    )?
+    .with_roles(vec![admin, editor, admin]);
+    assert_eq!(user.roles().len(), 2);
+
+    let mut user = user;
+    user.add_roles([admin, editor]);
+    assert_eq!(user.roles().len(), 2);
+    assert_eq!(user.updated_at(), None);
+
+    user.add_roles([editor, RoleId::new()]);
+    assert_eq!(user.roles().len(), 3);
+    assert!(user.updated_at().is_some());
+    Ok(())
+  }
+
   #[test]
   fn remove_role_from_user() -> Result<(), Box<dyn std::error::Error>> {
     let role = Role::new(RoleName::try_from("admin")?);
@@ -372,4 +1570,922 @@ mod tests {
     assert_eq!(user.roles().len(), 0);
     Ok(())
   }
+
+  #[test]
+  fn removing_absent_roles_does_not_touch()
+  -> Result<(), Box<dyn std::error::Error>> {
+    let mut user = User::new(
+      UserName::new("bob"),
+      UserPassword::new("password", "password")?,
+    )?;
+    user.remove_role(RoleId::new()).clear_roles();
+    assert_eq!(user.updated_at(), None);
+    Ok(())
+  }
+
+  #[test]
+  fn wrong_password_is_rejected() -> Result<(), Box<dyn std::error::Error>> {
+    // Regression test: verify_password used to check is_ok() on the verify
+    // result, which accepted every password.
+    let user = User::new(
+      UserName::new("bob"),
+      UserPassword::new("password", "password")?,
+    )?;
+    assert!(user.verify_password("password"));
+    assert!(!user.password().verify("wrong")?);
+    assert!(!user.verify_password("wrong"));
+    assert!(!user.verify_password(""));
+    assert!(!user.verify_password("Password"));
+    Ok(())
+  }
+
+  #[test]
+  fn user_id_uuid_round_trip() {
+    let uuid = uuid::Uuid::new_v4();
+    let id = UserId::from(uuid);
+    assert_eq!(id.as_uuid(), uuid);
+  }
+
+  #[test]
+  fn user_id_from_u128_is_stable() {
+    assert_eq!(UserId::from_u128(1), UserId::from_u128(1));
+    assert_ne!(UserId::from_u128(1), UserId::from_u128(2));
+    assert_eq!(UserId::from_u128(0), UserId::nil());
+    assert_eq!(
+      UserId::from_u128(1).to_string(),
+      "00000000-0000-0000-0000-000000000001"
+    );
+  }
+
+  #[cfg(feature = "sqlx")]
+  #[test]
+  fn bind_user_id_in_query() {
+    let mut query = sqlx::QueryBuilder::<sqlx::Postgres>::new(
+      "SELECT * FROM users WHERE id = ",
+    );
+    query.push_bind(UserId::from_u128(1));
+    assert_eq!(query.sql(), "SELECT * FROM users WHERE id = $1");
+  }
+
+  #[test]
+  fn user_name_as_str() {
+    let name = UserName::new("bob");
+    assert_eq!(name.as_str(), "bob");
+    assert!(std::ptr::eq(name.as_str(), name.clone().as_str()));
+  }
+
+  #[test]
+  fn compare_user_name_with_str() -> Result<(), Box<dyn std::error::Error>> {
+    let user = User::new(
+      UserName::new("bob"),
+      UserPassword::new("password", "password")?,
+    )?;
+    assert!(*user.name() == "bob");
+    assert!(*user.name() == *"bob");
+    assert!(*user.name() != "alice");
+    Ok(())
+  }
+
+  #[test]
+  fn user_name_nfc_normalization() {
+    let composed = UserName::new("Ren\u{e9}");
+    let decomposed = UserName::new("Rene\u{301}");
+    assert_eq!(composed, decomposed);
+    assert_eq!(decomposed.as_str(), "Ren\u{e9}");
+  }
+
+  #[test]
+  fn deserialized_user_name_is_normalized()
+  -> Result<(), Box<dyn std::error::Error>> {
+    let name: UserName = serde_json::from_str("\"Rene\u{301}\"")?;
+    assert_eq!(name, UserName::new("Ren\u{e9}"));
+    Ok(())
+  }
+
+  #[test]
+  fn user_name_normalized() {
+    assert_eq!(UserName::new(" Bob ").normalized(), "bob");
+  }
+
+  #[test]
+  fn user_name_eq_ignore_case() {
+    let upper = UserName::new("Bob");
+    let lower = UserName::new("bob");
+    assert_ne!(upper, lower);
+    assert!(upper.eq_ignore_case(&lower));
+    assert!(!upper.eq_ignore_case(&UserName::new("alice")));
+  }
+
+  #[test]
+  fn timestamps_are_utc() -> Result<(), Box<dyn std::error::Error>> {
+    let password = UserPassword::new("password", "password")?;
+    let before = chrono::Utc::now();
+    let mut user = User::new(UserName::new("bob"), password)?;
+    let after = chrono::Utc::now();
+    assert!(before <= *user.created_at() && *user.created_at() <= after);
+    user.touch();
+
+    let updated_at = user.updated_at().ok_or("updated_at not set")?;
+    let json = serde_json::to_string(updated_at)?;
+    assert!(json.ends_with("Z\""), "unexpected timestamp {json}");
+    let trimmed = json.trim_matches('"');
+    chrono::DateTime::parse_from_rfc3339(trimmed)?;
+    Ok(())
+  }
+
+  #[test]
+  fn public_user_omits_password() -> Result<(), Box<dyn std::error::Error>> {
+    let user = User::new(
+      UserName::new("bob"),
+      UserPassword::new("password", "password")?,
+    )?
+    .with_role(RoleId::new());
+    let json = serde_json::to_string(&user.to_public())?;
+    assert!(json.contains("\"bob\""));
+    assert!(!json.contains("argon2"));
+    assert!(!json.contains("password"));
+    Ok(())
+  }
+
+  #[test]
+  fn merge_user_versions() -> Result<(), Box<dyn std::error::Error>> {
+    let admin = RoleId::new();
+    let editor = RoleId::new();
+    let viewer = RoleId::new();
+    let mut local = User::new(
+      UserName::new("bob"),
+      UserPassword::new("password", "password")?,
+    )?
+    .with_roles([admin, editor]);
+
+    let mut remote = local.clone();
+    remote.clear_roles().add_roles([editor, viewer]);
+    remote.rename(UserName::new("robert"));
+    remote.set_password(UserPassword::new("new-password", "new-password")?);
+
+    local.merge(&remote)?;
+    assert_eq!(local.name(), &UserName::new("robert"));
+    assert_eq!(local.updated_at(), remote.updated_at());
+    assert!(local.verify_password("new-password"));
+    let ids: Vec<RoleId> = local.roles_iter().copied().collect();
+    assert_eq!(ids, vec![admin, editor, viewer]);
+    Ok(())
+  }
+
+  #[test]
+  fn merge_rejects_different_users() -> Result<(), Box<dyn std::error::Error>> {
+    let password = UserPassword::new("password", "password")?;
+    let mut bob = User::new(UserName::new("bob"), password.clone())?;
+    let alice = User::new(UserName::new("alice"), password)?;
+    let before = bob.clone();
+    assert_eq!(bob.merge(&alice), Err(UserError::IdMismatch));
+    assert_eq!(bob, before);
+    Ok(())
+  }
+
+  #[test]
+  fn validate_user_invariants() -> Result<(), Box<dyn std::error::Error>> {
+    let admin = RoleId::new();
+    let editor = RoleId::new();
+    let mut user = User::new(
+      UserName::new("bob"),
+      UserPassword::new("password", "password")?,
+    )?
+    .with_role(admin);
+    assert_eq!(user.validate(), Ok(()));
+
+    user.roles.push(UserRole::new(user.id(), admin));
+    user.roles.push(UserRole::new(UserId::new(), editor));
+    assert_eq!(
+      user.validate(),
+      Err(vec![
+        UserError::DuplicateRole(admin),
+        UserError::RoleUserMismatch(editor),
+      ])
+    );
+
+    user.name = UserName::new(" ");
+    user.password = UserPassword("not a hash".to_string());
+    user.roles.clear();
+    assert_eq!(
+      user.validate(),
+      Err(vec![UserError::EmptyName, UserError::CorruptHash])
+    );
+    Ok(())
+  }
+
+  #[test]
+  fn restore_user_from_snapshot() -> Result<(), Box<dyn std::error::Error>> {
+    let mut user = User::new(
+      UserName::new("bob"),
+      UserPassword::new("password", "password")?,
+    )?
+    .with_role(RoleId::new());
+    let original = user.clone();
+    let snapshot = user.snapshot();
+
+    user.rename(UserName::new("robert")).add_role(RoleId::new());
+    assert_ne!(user, original);
+
+    user.restore_from(snapshot)?;
+    assert_eq!(user, original);
+    for role in &original {
+      let restored =
+        user.user_role(role.role_id()).ok_or("role not restored")?;
+      assert_eq!(restored.assigned_at(), role.assigned_at());
+    }
+    Ok(())
+  }
+
+  #[test]
+  fn user_snapshot_round_trip() -> Result<(), Box<dyn std::error::Error>> {
+    let mut user = User::new(
+      UserName::new("bob"),
+      UserPassword::new("password", "password")?,
+    )?
+    .with_role(RoleId::from_u128(1))
+    .with_role(RoleId::from_u128(2));
+    user.touch();
+
+    let snapshot = UserSnapshot::from(&user);
+    assert_eq!(snapshot.id, user.id().to_string());
+    assert_eq!(snapshot.name, "bob");
+    assert!(snapshot.password_hash.starts_with("$argon2id$"));
+    assert_eq!(snapshot.roles.len(), 2);
+    assert!(snapshot.created_at.ends_with('Z'));
+
+    let json = serde_json::to_string(&snapshot)?;
+    let parsed: UserSnapshot = serde_json::from_str(&json)?;
+    let restored = User::try_from(parsed)?;
+    assert_eq!(restored, user);
+    for role in &user {
+      let restored_role = restored
+        .user_role(role.role_id())
+        .ok_or("role not restored")?;
+      assert_eq!(restored_role.assigned_at(), role.assigned_at());
+    }
+    assert!(restored.verify_password("password"));
+    Ok(())
+  }
+
+  #[test]
+  fn reject_malformed_user_snapshot() -> Result<(), Box<dyn std::error::Error>>
+  {
+    let user = User::new(
+      UserName::new("bob"),
+      UserPassword::new("password", "password")?,
+    )?;
+    let mut snapshot = user.snapshot();
+    snapshot.id = "not-a-uuid".to_string();
+    assert_eq!(
+      User::try_from(snapshot),
+      Err(UserError::InvalidId("not-a-uuid".to_string()))
+    );
+
+    let mut snapshot = user.snapshot();
+    snapshot.created_at = "yesterday".to_string();
+    assert_eq!(
+      User::try_from(snapshot),
+      Err(UserError::InvalidTimestamp("yesterday".to_string()))
+    );
+
+    let mut snapshot = user.snapshot();
+    snapshot.password_hash = "garbage".to_string();
+    assert_eq!(User::try_from(snapshot), Err(UserError::CorruptHash));
+    Ok(())
+  }
+
+  #[test]
+  fn display_user_summary() -> Result<(), Box<dyn std::error::Error>> {
+    let user = User::new(
+      UserName::new("bob"),
+      UserPassword::new("password", "password")?,
+    )?
+    .with_role(RoleId::new())
+    .with_role(RoleId::new());
+    let summary = user.to_string();
+    assert_eq!(summary, format!("bob ({}) [2 roles]", user.id()));
+    assert!(!summary.contains("argon2"));
+    Ok(())
+  }
+
+  #[test]
+  fn password_confirmation() {
+    assert!(UserPassword::new("password", "password").is_ok());
+    assert_eq!(
+      UserPassword::new("password", "passw0rd"),
+      Err(UserError::PasswordMismatch)
+    );
+    assert_eq!(
+      UserPassword::new("password", "password1"),
+      Err(UserError::PasswordMismatch)
+    );
+  }
+
+  #[test]
+  fn password_from_phc() -> Result<(), Box<dyn std::error::Error>> {
+    let original = UserPassword::new("password", "password")?;
+    let rehydrated = UserPassword::from_phc(&original.0)?;
+    assert_eq!(rehydrated, original);
+    assert!(rehydrated.verify("password")?);
+    assert_eq!(
+      UserPassword::from_phc("garbage"),
+      Err(UserError::CorruptHash)
+    );
+    Ok(())
+  }
+
+  #[test]
+  fn password_with_secret() -> Result<(), Box<dyn std::error::Error>> {
+    let secret = b"application-pepper";
+    let password =
+      UserPassword::new_with_secret("password", "password", secret)?;
+    assert!(password.verify_with_secret("password", secret)?);
+    assert!(!password.verify_with_secret("wrong", secret)?);
+    assert!(!password.verify_with_secret("password", b"other-pepper")?);
+    assert!(!password.verify("password")?);
+    Ok(())
+  }
+
+  #[test]
+  fn password_meets_minimum_cost() -> Result<(), Box<dyn std::error::Error>> {
+    let weak = Argon2::new(
+      argon2::Algorithm::Argon2id,
+      argon2::Version::V0x13,
+      argon2::Params::new(8, 1, 1, None)?,
+    );
+    let salt = SaltString::generate(&mut OsRng);
+    let weak = UserPassword::from_phc(
+      &weak.hash_password(b"password", &salt)?.to_string(),
+    )?;
+    assert!(!weak.meets_minimum(&argon2::Params::default())?);
+
+    let compliant = UserPassword::new("password", "password")?;
+    assert!(compliant.meets_minimum(&argon2::Params::default())?);
+    assert!(!compliant.meets_minimum(&argon2::Params::new(
+      argon2::Params::DEFAULT_M_COST * 2,
+      argon2::Params::DEFAULT_T_COST,
+      argon2::Params::DEFAULT_P_COST,
+      None,
+    )?)?);
+    Ok(())
+  }
+
+  #[test]
+  fn upgrade_weak_password_on_verify() -> Result<(), Box<dyn std::error::Error>>
+  {
+    let weak = Argon2::new(
+      argon2::Algorithm::Argon2id,
+      argon2::Version::V0x13,
+      argon2::Params::new(8, 1, 1, None)?,
+    );
+    let salt = SaltString::generate(&mut OsRng);
+    let weak = UserPassword::from_phc(
+      &weak.hash_password(b"password", &salt)?.to_string(),
+    )?;
+    let policy = argon2::Params::new(16, 2, 1, None)?;
+
+    assert_eq!(weak.verify_and_upgrade("wrong", &policy)?, None);
+    assert_eq!(
+      weak
+        .verify_and_upgrade("password", &argon2::Params::new(8, 1, 1, None)?)?,
+      None
+    );
+    let upgraded = weak
+      .verify_and_upgrade("password", &policy)?
+      .ok_or("expected an upgraded hash")?;
+    assert!(upgraded.meets_minimum(&policy)?);
+    assert!(upgraded.verify("password")?);
+    Ok(())
+  }
+
+  #[test]
+  fn corrupt_hash_fails_minimum_check() {
+    let password = UserPassword("not a hash".to_string());
+    assert_eq!(
+      password.meets_minimum(&argon2::Params::default()),
+      Err(UserError::CorruptHash)
+    );
+  }
+
+  #[test]
+  fn relative_argon2_cost() -> Result<(), Box<dyn std::error::Error>> {
+    let reference = argon2::Params::new(19_456, 2, 1, None)?;
+    let doubled = argon2::Params::new(38_912, 2, 1, None)?;
+    assert!((relative_cost(&doubled, &reference) - 2.0).abs() < 1e-9);
+    assert!((relative_cost(&reference, &doubled) - 0.5).abs() < 1e-9);
+    Ok(())
+  }
+
+  #[test]
+  fn match_user_error_with_wildcard() {
+    // Downstream crates must include a wildcard arm; new variants may be
+    // added without a breaking release.
+    let describe = |err: &UserError| match err {
+      UserError::PasswordMismatch => "mismatch",
+      UserError::InvalidPassword => "invalid",
+      _ => "other",
+    };
+    assert_eq!(describe(&UserError::PasswordMismatch), "mismatch");
+    assert_eq!(describe(&UserError::RateLimited), "other");
+  }
+
+  #[test]
+  fn hashing_failure_exposes_source() {
+    use std::error::Error;
+
+    let err =
+      UserError::HashingFailed(argon2::password_hash::Error::SaltInvalid(
+        argon2::password_hash::errors::InvalidValue::TooShort,
+      ));
+    let source = err.source().expect("hashing failure should have a source");
+    assert_eq!(
+      source.downcast_ref::<argon2::password_hash::Error>(),
+      Some(&argon2::password_hash::Error::SaltInvalid(
+        argon2::password_hash::errors::InvalidValue::TooShort,
+      ))
+    );
+    assert!(UserError::PasswordMismatch.source().is_none());
+  }
+
+  #[test]
+  fn verify_user_password() -> Result<(), Box<dyn std::error::Error>> {
+    let user = User::new(
+      UserName::new("bob"),
+      UserPassword::new("password", "password")?,
+    )?;
+    assert!(user.verify_password("password"));
+    assert!(!user.verify_password("wrong"));
+    Ok(())
+  }
+
+  #[test]
+  fn rate_limit_password_verification() -> Result<(), Box<dyn std::error::Error>>
+  {
+    let mut user = User::new(
+      UserName::new("bob"),
+      UserPassword::new("password", "password")?,
+    )?;
+    let window = chrono::Duration::minutes(1);
+    assert_eq!(user.verify_rate_limited("wrong", 2, window), Ok(false));
+    assert_eq!(user.verify_rate_limited("password", 2, window), Ok(true));
+    assert_eq!(
+      user.verify_rate_limited("password", 2, window),
+      Err(UserError::RateLimited)
+    );
+
+    user.verify_attempts = user
+      .verify_attempts
+      .iter()
+      .map(|attempt| *attempt - chrono::Duration::minutes(2))
+      .collect();
+    assert_eq!(user.verify_rate_limited("password", 2, window), Ok(true));
+    Ok(())
+  }
+
+  #[test]
+  fn rate_limit_attempts_do_not_affect_equality()
+  -> Result<(), Box<dyn std::error::Error>> {
+    let mut user = User::new(
+      UserName::new("bob"),
+      UserPassword::new("password", "password")?,
+    )?;
+    let original = user.clone();
+    user.verify_rate_limited("wrong", 1, chrono::Duration::minutes(1))?;
+    assert_eq!(user, original);
+    Ok(())
+  }
+
+  #[test]
+  fn restore_keeps_rate_limit() -> Result<(), Box<dyn std::error::Error>> {
+    let mut user = User::new(
+      UserName::new("bob"),
+      UserPassword::new("password", "password")?,
+    )?;
+    let snapshot = user.snapshot();
+    let window = chrono::Duration::minutes(1);
+    user.verify_rate_limited("wrong", 1, window)?;
+
+    user.restore_from(snapshot)?;
+    assert_eq!(
+      user.verify_rate_limited("password", 1, window),
+      Err(UserError::RateLimited)
+    );
+    Ok(())
+  }
+
+  #[test]
+  fn user_external_ref() -> Result<(), Box<dyn std::error::Error>> {
+    let mut user = User::new(
+      UserName::new("bob"),
+      UserPassword::new("password", "password")?,
+    )?;
+    user.id = UserId::from_u128(1);
+    assert_eq!(user.external_ref(), "aaaaaaaaaaaaaaaaaaaaaaaaae");
+
+    user.id = UserId::new();
+    let reference = user.external_ref();
+    assert_eq!(reference.len(), 26);
+    assert!(
+      reference
+        .chars()
+        .all(|c| c.is_ascii_lowercase() || ('2'..='7').contains(&c))
+    );
+    assert_eq!(reference, user.external_ref());
+    Ok(())
+  }
+
+  #[test]
+  fn parse_user_external_ref() -> Result<(), Box<dyn std::error::Error>> {
+    let user = User::new(
+      UserName::new("bob"),
+      UserPassword::new("password", "password")?,
+    )?;
+    assert_eq!(UserId::from_external_ref(&user.external_ref())?, user.id());
+    assert_eq!(
+      UserId::from_external_ref("not-a-ref"),
+      Err(UserError::InvalidExternalRef)
+    );
+    assert_eq!(
+      UserId::from_external_ref("aaaa"),
+      Err(UserError::InvalidExternalRef)
+    );
+    Ok(())
+  }
+
+  #[test]
+  fn touch_user() -> Result<(), Box<dyn std::error::Error>> {
+    let mut user = User::new(
+      UserName::new("bob"),
+      UserPassword::new("password", "password")?,
+    )?;
+    assert_eq!(user.updated_at(), None);
+
+    user.touch();
+    let first = *user.updated_at().ok_or("updated_at not set")?;
+    user.touch();
+    let second = *user.updated_at().ok_or("updated_at not set")?;
+    assert!(second >= first);
+    Ok(())
+  }
+
+  #[test]
+  fn user_role_assigned_at() {
+    let before = chrono::Utc::now();
+    let user_id = UserId::new();
+    let role_id = RoleId::new();
+    let first = UserRole::new(user_id, role_id);
+    assert!(*first.assigned_at() >= before);
+
+    let mut second = UserRole::new(user_id, role_id);
+    second.assigned_at = *first.assigned_at() + chrono::Duration::hours(1);
+    assert_ne!(first.assigned_at(), second.assigned_at());
+    assert_eq!(first, second);
+  }
+
+  #[test]
+  fn look_up_user_role() -> Result<(), Box<dyn std::error::Error>> {
+    let admin = RoleId::new();
+    let user = User::new(
+      UserName::new("bob"),
+      UserPassword::new("password", "password")?,
+    )?
+    .with_role(admin);
+    let assignment = user.user_role(&admin).ok_or("role not assigned")?;
+    assert_eq!(assignment.role_id(), &admin);
+    assert_eq!(assignment.user_id(), &user.id());
+    assert_eq!(user.user_role(&RoleId::new()), None);
+    Ok(())
+  }
+
+  #[test]
+  fn iterate_over_user() -> Result<(), Box<dyn std::error::Error>> {
+    let user = User::new(
+      UserName::new("bob"),
+      UserPassword::new("password", "password")?,
+    )?
+    .with_role(RoleId::new())
+    .with_role(RoleId::new());
+    let mut count = 0;
+    for role in &user {
+      assert_eq!(role.user_id(), &user.id());
+      count += 1;
+    }
+    assert_eq!(count, 2);
+    Ok(())
+  }
+
+  #[test]
+  fn count_user_roles() -> Result<(), Box<dyn std::error::Error>> {
+    let user = User::new(
+      UserName::new("bob"),
+      UserPassword::new("password", "password")?,
+    )?;
+    assert_eq!(user.role_count(), 0);
+    assert!(!user.has_any_role());
+
+    let user = user.with_role(RoleId::new()).with_role(RoleId::new());
+    assert_eq!(user.role_count(), 2);
+    assert!(user.has_any_role());
+    Ok(())
+  }
+
+  #[test]
+  fn iterate_user_role_ids() -> Result<(), Box<dyn std::error::Error>> {
+    let admin = RoleId::new();
+    let editor = RoleId::new();
+    let user = User::new(
+      UserName::new("bob"),
+      UserPassword::new("password", "password")?,
+    )?
+    .with_role(admin)
+    .with_role(editor);
+    let ids: Vec<RoleId> = user.roles_iter().copied().collect();
+    assert_eq!(ids, vec![admin, editor]);
+    Ok(())
+  }
+
+  #[test]
+  fn resolve_user_roles() -> Result<(), Box<dyn std::error::Error>> {
+    let admin = Role::new(RoleName::try_from("admin")?);
+    let editor = Role::new(RoleName::try_from("editor")?);
+    let mut repo = InMemoryRoleRepository::new();
+    repo.insert(admin.clone());
+    repo.insert(editor.clone());
+
+    let user = User::new(
+      UserName::new("bob"),
+      UserPassword::new("password", "password")?,
+    )?
+    .with_role(admin.id())
+    .with_role(editor.id());
+    assert_eq!(
+      user.resolve_roles(&repo),
+      vec![admin.clone(), editor.clone()]
+    );
+
+    repo.remove(&editor.id());
+    assert_eq!(user.resolve_roles(&repo), vec![admin]);
+    Ok(())
+  }
+
+  #[test]
+  fn user_role_set() -> Result<(), Box<dyn std::error::Error>> {
+    let admin = RoleId::new();
+    let editor = RoleId::new();
+    let user = User::new(
+      UserName::new("bob"),
+      UserPassword::new("password", "password")?,
+    )?
+    .with_role(admin)
+    .with_role(admin)
+    .with_role(editor);
+    let set = user.role_set();
+    assert_eq!(set.len(), 2);
+    assert!(set.intersects(&[editor]));
+    Ok(())
+  }
+
+  #[test]
+  fn rank_roles_by_usage() -> Result<(), Box<dyn std::error::Error>> {
+    let admin = Role::new(RoleName::try_from("admin")?);
+    let editor = Role::new(RoleName::try_from("editor")?);
+    let viewer = Role::new(RoleName::try_from("viewer")?);
+    let password = UserPassword::new("password", "password")?;
+    let users = vec![
+      User::new(UserName::new("alice"), password.clone())?
+        .with_role(viewer.id())
+        .with_role(editor.id()),
+      User::new(UserName::new("bob"), password.clone())?.with_role(viewer.id()),
+      User::new(UserName::new("carol"), password)?
+        .with_role(viewer.id())
+        .with_role(viewer.id()),
+    ];
+    let usage =
+      roles_by_usage(&[admin.clone(), editor.clone(), viewer.clone()], &users);
+    assert_eq!(
+      usage,
+      vec![(viewer.id(), 3), (editor.id(), 1), (admin.id(), 0)]
+    );
+    Ok(())
+  }
+
+  #[test]
+  fn detect_privileged_role_gain() -> Result<(), Box<dyn std::error::Error>> {
+    let admin = RoleId::new();
+    let viewer = RoleId::new();
+    let privileged = HashSet::from([admin]);
+    let before = User::new(
+      UserName::new("bob"),
+      UserPassword::new("password", "password")?,
+    )?;
+
+    let promoted = before.clone().with_role(admin);
+    assert!(gained_privileged_role(&before, &promoted, &privileged));
+
+    let widened = before.clone().with_role(viewer);
+    assert!(!gained_privileged_role(&before, &widened, &privileged));
+    Ok(())
+  }
+
+  #[test]
+  fn paginate_user_collection() -> Result<(), Box<dyn std::error::Error>> {
+    let password = UserPassword::new("password", "password")?;
+    let users = ["alice", "bob", "carol", "dave", "erin"]
+      .into_iter()
+      .map(|name| User::new(UserName::new(name), password.clone()))
+      .collect::<Result<Vec<_>, _>>()?;
+
+    let first = paginate_users(&users, 0, 2);
+    assert_eq!(first, &users[0..2]);
+
+    let middle = paginate_users(&users, 1, 2);
+    assert_eq!(middle, &users[2..4]);
+
+    let last = paginate_users(&users, 2, 2);
+    assert_eq!(last, &users[4..5]);
+
+    assert!(paginate_users(&users, 3, 2).is_empty());
+    assert!(paginate_users(&users, usize::MAX, 2).is_empty());
+    Ok(())
+  }
+
+  #[test]
+  fn sort_users_by_creation() -> Result<(), Box<dyn std::error::Error>> {
+    let password = UserPassword::new("password", "password")?;
+    let base = chrono::Utc::now();
+    let mut alice = User::new(UserName::new("alice"), password.clone())?;
+    alice.created_at = base;
+    alice.id = UserId::from_u128(2);
+    let mut bob = User::new(UserName::new("bob"), password.clone())?;
+    bob.created_at = base;
+    bob.id = UserId::from_u128(1);
+    let mut carol = User::new(UserName::new("carol"), password)?;
+    carol.created_at = base - chrono::Duration::days(1);
+
+    let mut users = [alice, bob, carol];
+    users.sort();
+    let names: Vec<String> =
+      users.iter().map(|u| u.name().to_string()).collect();
+    assert_eq!(names, vec!["carol", "bob", "alice"]);
+    Ok(())
+  }
+
+  #[test]
+  fn user_order_is_consistent_with_eq() -> Result<(), Box<dyn std::error::Error>>
+  {
+    let original = User::new(
+      UserName::new("bob"),
+      UserPassword::new("password", "password")?,
+    )?;
+    let mut renamed = original.clone();
+    renamed.rename(UserName::new("robert"));
+    assert_ne!(original, renamed);
+    assert_ne!(original.cmp(&renamed), std::cmp::Ordering::Equal);
+    assert_eq!(original.cmp(&original.clone()), std::cmp::Ordering::Equal);
+
+    let set: std::collections::BTreeSet<User> =
+      [original.clone(), renamed.clone()].into_iter().collect();
+    assert_eq!(set.len(), 2);
+    assert!(set.contains(&original) && set.contains(&renamed));
+    Ok(())
+  }
+
+  #[test]
+  fn try_remove_user_role() -> Result<(), Box<dyn std::error::Error>> {
+    let admin = RoleId::new();
+    let mut user = User::new(
+      UserName::new("bob"),
+      UserPassword::new("password", "password")?,
+    )?
+    .with_role(admin);
+    assert!(!user.try_remove_role(RoleId::new()));
+    assert_eq!(user.updated_at(), None);
+    assert!(user.try_remove_role(admin));
+    assert!(user.roles().is_empty());
+    assert!(!user.try_remove_role(admin));
+    Ok(())
+  }
+
+  #[test]
+  fn require_and_remove_user_role() -> Result<(), Box<dyn std::error::Error>> {
+    let admin = RoleId::new();
+    let missing = RoleId::new();
+    let mut user = User::new(
+      UserName::new("bob"),
+      UserPassword::new("password", "password")?,
+    )?
+    .with_role(admin);
+    assert_eq!(user.require_and_remove_role(admin), Ok(()));
+    assert!(user.roles().is_empty());
+    assert_eq!(
+      user.require_and_remove_role(missing),
+      Err(UserError::RoleNotFound(missing))
+    );
+    Ok(())
+  }
+
+  #[test]
+  fn clear_user_roles() -> Result<(), Box<dyn std::error::Error>> {
+    let mut user = User::new(
+      UserName::new("bob"),
+      UserPassword::new("password", "password")?,
+    )?
+    .with_role(RoleId::new())
+    .with_role(RoleId::new());
+    user.clear_roles();
+    assert!(user.roles().is_empty());
+    assert!(user.updated_at().is_some());
+    Ok(())
+  }
+
+  #[test]
+  fn find_account_age_extremes() -> Result<(), Box<dyn std::error::Error>> {
+    assert_eq!(account_age_extremes(&[]), None);
+
+    let password = UserPassword::new("password", "password")?;
+    let base = chrono::Utc::now();
+    let mut users = ["alice", "bob", "carol"]
+      .into_iter()
+      .map(|name| User::new(UserName::new(name), password.clone()))
+      .collect::<Result<Vec<_>, _>>()?;
+    users[0].created_at = base - chrono::Duration::days(1);
+    users[1].created_at = base - chrono::Duration::days(3);
+    users[2].created_at = base;
+
+    let (oldest, newest) =
+      account_age_extremes(&users).ok_or("expected extremes")?;
+    assert_eq!(oldest.name(), &UserName::new("bob"));
+    assert_eq!(newest.name(), &UserName::new("carol"));
+    Ok(())
+  }
+
+  #[test]
+  fn check_user_creation_constraints() -> Result<(), Box<dyn std::error::Error>>
+  {
+    let admin = RoleId::new();
+    let editor = RoleId::new();
+    let password = UserPassword::new("password", "password")?;
+    let existing = vec![User::new(UserName::new("Bob"), password.clone())?];
+    let constraints = CreationConstraints::new()
+      .with_max_roles(1)
+      .with_allowed_roles([admin, editor]);
+
+    let candidate =
+      User::new(UserName::new("alice"), password.clone())?.with_role(editor);
+    assert_eq!(can_create_user(&candidate, &existing, &constraints), Ok(()));
+
+    let candidate = User::new(UserName::new("bob"), password)?
+      .with_role(admin)
+      .with_role(editor);
+    assert_eq!(
+      can_create_user(&candidate, &existing, &constraints),
+      Err(vec![
+        UserError::NameTaken,
+        UserError::TooManyRoles { max: 1, actual: 2 },
+      ])
+    );
+    Ok(())
+  }
+
+  #[test]
+  fn set_user_password() -> Result<(), Box<dyn std::error::Error>> {
+    let mut user = User::new(
+      UserName::new("bob"),
+      UserPassword::new("password", "password")?,
+    )?;
+    let original = *user.password_changed_at();
+    user.set_password(UserPassword::new("new-password", "new-password")?);
+    assert!(user.verify_password("new-password"));
+    assert!(*user.password_changed_at() >= original);
+    assert!(user.updated_at().is_some());
+    Ok(())
+  }
+
+  #[test]
+  fn password_expiry() -> Result<(), Box<dyn std::error::Error>> {
+    let mut user = User::new(
+      UserName::new("bob"),
+      UserPassword::new("password", "password")?,
+    )?;
+    let max_age = chrono::Duration::days(90);
+    assert!(!user.is_password_expired(max_age));
+
+    user.password_changed_at = chrono::Utc::now() - chrono::Duration::days(91);
+    assert!(user.password_age() >= chrono::Duration::days(91));
+    assert!(user.is_password_expired(max_age));
+    Ok(())
+  }
+
+  #[test]
+  fn rename_user() -> Result<(), Box<dyn std::error::Error>> {
+    let mut user = User::new(
+      UserName::new("bob"),
+      UserPassword::new("password", "password")?,
+    )?;
+    user.rename(UserName::new("robert"));
+    assert_eq!(user.name(), &UserName::new("robert"));
+    assert!(user.updated_at().is_some());
+    Ok(())
+  }
 }